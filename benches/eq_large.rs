@@ -0,0 +1,28 @@
+// Benchmarking equality on a large input
+//
+// Comparing two clones should not need to look at the bytes
+// while comparing two separate allocations does
+
+use shared_string::SharedString;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn benchmark_eq_large(c: &mut Criterion) {
+	let s = "a".repeat(4 * 1024 * 1024);
+	let mut a = SharedString::from(s.clone());
+	let _rest = a.split_off(2 * 1024 * 1024);
+	let same = a.clone();
+	let copy = SharedString::from(a.as_str());
+
+	c.bench_function("eq_same_bytes", |b| {
+		b.iter(|| black_box(&a) == black_box(&same))
+	});
+
+	c.bench_function("eq_different_bytes", |b| {
+		b.iter(|| black_box(&a) == black_box(&copy))
+	});
+}
+
+criterion_group!(bench_eq_large, benchmark_eq_large);
+
+criterion_main!(bench_eq_large);
@@ -10,6 +10,7 @@ use shared_string::SharedString;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+#[allow(dead_code)]
 struct NameString {
 	pub firstname: String,
 	pub middlename: String,
@@ -27,6 +28,7 @@ impl NameString {
 	}
 }
 
+#[allow(dead_code)]
 struct NameShared {
 	pub firstname: SharedString,
 	pub middlename: SharedString,
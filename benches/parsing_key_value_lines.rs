@@ -10,7 +10,7 @@ use shared_string::{SharedString, SharedSyncString};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 // taken from a response from wikipedia.org
-const HTTP_HEADER: &'static str = "\
+const HTTP_HEADER: &str = "\
 date: Mon, 30 Nov 2020 22:16:22 GMT
 server: mw1271.eqiad.wmnet
 x-content-type-options: nosniff
@@ -200,6 +200,26 @@ fn parse_to_shared_sync_string_from_buf_reader<T: Read>(
 	map
 }
 
+// reads everything into one allocation which is shared by all lines
+fn parse_to_shared_string_from_reader_all<T: Read>(
+	reader: BufReader<T>
+) -> HashMap<SharedString, SharedString> {
+	let string = SharedString::from_reader_all(reader).unwrap();
+	let mut map = HashMap::new();
+	for line in string.lines() {
+		// unwrap because we know that in every line is a colon
+		let at = line.find(':').unwrap();
+
+		let key = line.idx(..at);
+		// we can skip the space here because we know after every colon is a space
+		let value = line.idx((at + 2)..);
+
+		map.insert(key, value);
+	}
+
+	map
+}
+
 fn benchmark_buf_reader(c: &mut Criterion) {
 	let bytes = HTTP_HEADER.as_bytes().to_vec();
 
@@ -233,6 +253,13 @@ fn benchmark_buf_reader(c: &mut Criterion) {
 			parse_to_shared_sync_string_from_buf_reader(black_box(reader))
 		})
 	});
+
+	c.bench_function("parse_to_shared_string_from_reader_all", |b| {
+		b.iter(|| {
+			let reader = BufReader::new(bytes.as_slice());
+			parse_to_shared_string_from_reader_all(black_box(reader))
+		})
+	});
 }
 
 // BufReader with a new string for every line
@@ -0,0 +1,44 @@
+// Benchmarking split and lines on a large input
+//
+// Run with `--no-default-features` to compare
+// against the implementation without memchr
+
+use shared_string::SharedString;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// generates a log file with around 4MB
+fn log_file() -> String {
+	let mut log = String::with_capacity(4 * 1024 * 1024);
+	let mut i = 0;
+	while log.len() < 4 * 1024 * 1024 {
+		log.push_str(&format!(
+			"2020-11-30 22:16:{:02} INFO request {} handled in {}ms \
+			with status 200 by worker {}\n",
+			i % 60, i, i % 1000, i % 16
+		));
+		i += 1;
+	}
+
+	log
+}
+
+fn benchmark_split_large(c: &mut Criterion) {
+	let log = SharedString::from(log_file());
+
+	c.bench_function("split_large", |b| {
+		b.iter(|| {
+			black_box(log.clone()).split(b'\n').count()
+		})
+	});
+
+	c.bench_function("lines_large", |b| {
+		b.iter(|| {
+			black_box(log.clone()).lines().count()
+		})
+	});
+}
+
+criterion_group!(bench_split_large, benchmark_split_large);
+
+criterion_main!(bench_split_large);
@@ -0,0 +1,42 @@
+//! Arbitrary implementation
+
+use crate::{SharedGenString, RefCounter};
+
+use arbitrary::{Arbitrary, Unstructured, Result};
+
+impl<'a, R> Arbitrary<'a> for SharedGenString<R>
+where R: RefCounter {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		<&str as Arbitrary>::arbitrary(u).map(Into::into)
+	}
+
+	fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+		<&str as Arbitrary>::arbitrary_take_rest(u).map(Into::into)
+	}
+
+	#[inline]
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		<&str as Arbitrary>::size_hint(depth)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{SharedString, SharedSyncString};
+
+	use arbitrary::{Arbitrary, Unstructured};
+
+	#[test]
+	fn from_unstructured() {
+		let data = b"hello world\xff";
+		let mut u = Unstructured::new(data);
+		let s = SharedString::arbitrary(&mut u).unwrap();
+		assert!(data.starts_with(s.as_bytes()));
+
+		let u = Unstructured::new(b"foo bar");
+		let s = SharedSyncString::arbitrary_take_rest(u).unwrap();
+		assert_eq!(s, "foo bar");
+		assert_eq!(s.split(b' ').count(), 2);
+	}
+}
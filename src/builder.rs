@@ -0,0 +1,133 @@
+//! Builder type
+
+use crate::{SharedGenString, RefCounter};
+
+use core::fmt;
+use alloc::string::String;
+
+/// A builder to construct a `SharedString` piece by piece.
+///
+/// After the string is built it can be converted with
+/// [freeze](#method.freeze) into a `SharedString` or a `SharedSyncString`.
+///
+/// ## Example
+///
+/// ```
+/// use shared_string::{SharedString, SharedStringBuilder};
+///
+/// let mut builder = SharedStringBuilder::with_capacity(7);
+/// builder.push_str("foo");
+/// builder.push(' ');
+/// builder.push_str("bar");
+///
+/// let foobar: SharedString = builder.freeze();
+/// assert_eq!(foobar, "foo bar");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SharedStringBuilder {
+	inner: String
+}
+
+impl SharedStringBuilder {
+	/// Creates a new empty `SharedStringBuilder`.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new empty `SharedStringBuilder` with at least the specified
+	/// capacity.
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			inner: String::with_capacity(capacity)
+		}
+	}
+
+	/// Appends a char to the end.
+	#[inline]
+	pub fn push(&mut self, ch: char) {
+		self.inner.push(ch);
+	}
+
+	/// Appends a string slice to the end.
+	#[inline]
+	pub fn push_str(&mut self, string: &str) {
+		self.inner.push_str(string);
+	}
+
+	/// Returns the len in bytes.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	/// Returns `true` if the length is zero, and `false` otherwise.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	/// Returns a string slice of everything that was pushed until now.
+	#[inline]
+	pub fn as_str(&self) -> &str {
+		&self.inner
+	}
+
+	/// Converts the builder into a `SharedString`.
+	///
+	/// The bytes are not copied, but the allocation might get shrunk to fit
+	/// the length.
+	#[inline]
+	pub fn freeze<R>(self) -> SharedGenString<R>
+	where R: RefCounter {
+		self.inner.into()
+	}
+}
+
+/// Allows to use `write!` with a `SharedStringBuilder`.
+///
+/// ## Example
+///
+/// ```
+/// use shared_string::{SharedString, SharedStringBuilder};
+/// use std::fmt::Write;
+///
+/// let mut builder = SharedStringBuilder::new();
+/// write!(builder, "{}-{}", "foo", 42).unwrap();
+///
+/// let s: SharedString = builder.freeze();
+/// assert_eq!(s, "foo-42");
+/// ```
+impl fmt::Write for SharedStringBuilder {
+	#[inline]
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.push_str(s);
+		Ok(())
+	}
+
+	#[inline]
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		self.push(c);
+		Ok(())
+	}
+}
+
+impl<R> From<SharedStringBuilder> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from(builder: SharedStringBuilder) -> Self {
+		builder.freeze()
+	}
+}
+
+impl<R> From<SharedGenString<R>> for SharedStringBuilder
+where R: RefCounter {
+	/// See [into_builder](struct.SharedGenString.html#method.into_builder).
+	#[inline]
+	fn from(s: SharedGenString<R>) -> Self {
+		Self {
+			inner: s.into_string()
+		}
+	}
+}
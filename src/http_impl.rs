@@ -0,0 +1,68 @@
+//! Http implementations
+
+use crate::{SharedGenString, RefCounter};
+
+use core::convert::TryFrom;
+
+use http::header::{
+	HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue
+};
+
+/// Avoids a copy if the underlying bytes are not used by another
+/// `SharedString` and start at zero.
+impl<R> TryFrom<SharedGenString<R>> for HeaderValue
+where R: RefCounter {
+	type Error = InvalidHeaderValue;
+
+	#[inline]
+	fn try_from(s: SharedGenString<R>) -> Result<Self, Self::Error> {
+		HeaderValue::try_from(s.into_bytes())
+	}
+}
+
+impl<R> TryFrom<SharedGenString<R>> for HeaderName
+where R: RefCounter {
+	type Error = InvalidHeaderName;
+
+	#[inline]
+	fn try_from(s: SharedGenString<R>) -> Result<Self, Self::Error> {
+		HeaderName::from_bytes(s.as_bytes())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use crate::SharedString;
+
+	use core::convert::TryFrom;
+
+	use http::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+
+	#[test]
+	fn header_lines() {
+		let raw = SharedString::from(
+			"Content-Type: text/html\r\nX-Custom: foo bar\r\n"
+		);
+		let headers: Vec<_> = raw.lines()
+			.map(|line| {
+				let (name, value) = line.split_once(b':').unwrap();
+				(
+					HeaderName::try_from(name).unwrap(),
+					HeaderValue::try_from(value.trim()).unwrap()
+				)
+			})
+			.collect();
+
+		assert_eq!(headers[0].0, CONTENT_TYPE);
+		assert_eq!(headers[0].1, "text/html");
+		assert_eq!(headers[1].0, "x-custom");
+		assert_eq!(headers[1].1, "foo bar");
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(HeaderName::try_from(SharedString::from("a b")).is_err());
+		assert!(HeaderValue::try_from(SharedString::from("a\nb")).is_err());
+	}
+}
@@ -0,0 +1,212 @@
+//! Helpers to read a `SharedString`
+//!
+//! Only available with the `std` feature.
+
+use crate::{SharedGenString, RefCounter};
+
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+// converts the utf8 error into an io error
+pub(crate) fn from_utf8<R>(bytes: Vec<u8>) -> io::Result<SharedGenString<R>>
+where R: RefCounter {
+	SharedGenString::from_utf8(bytes)
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Returns an iterator which reads every line into a `SharedString`.
+///
+/// Lines are split the same way as in
+/// [lines](../struct.SharedGenString.html#method.lines). Every line is read
+/// directly into its own `SharedString` without another copy.
+///
+/// ## Errors
+///
+/// Returns an error with the kind `InvalidData` if a line is not valid
+/// UTF-8.
+///
+/// ## Example
+///
+/// ```
+/// # use shared_string::SharedString;
+/// use shared_string::io::shared_lines;
+/// use std::io::BufReader;
+///
+/// let reader = BufReader::new(&b"foo\r\nbar\n"[..]);
+/// let lines: Vec<SharedString> = shared_lines(reader)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(lines, ["foo", "bar"]);
+/// ```
+#[inline]
+pub fn shared_lines<T, R>(reader: T) -> SharedLines<T, R>
+where
+	T: BufRead,
+	R: RefCounter {
+	SharedLines {
+		reader,
+		marker: PhantomData
+	}
+}
+
+/// A SharedLines iterator returned by [shared_lines].
+#[derive(Debug)]
+pub struct SharedLines<T, R> {
+	reader: T,
+	marker: PhantomData<R>
+}
+
+impl<T, R> Iterator for SharedLines<T, R>
+where
+	T: BufRead,
+	R: RefCounter {
+	type Item = io::Result<SharedGenString<R>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut bytes = Vec::new();
+		match self.reader.read_until(b'\n', &mut bytes) {
+			Ok(0) => return None,
+			Ok(_) => {},
+			Err(e) => return Some(Err(e))
+		}
+
+		if bytes.last() == Some(&b'\n') {
+			bytes.pop();
+			if bytes.last() == Some(&b'\r') {
+				bytes.pop();
+			}
+		}
+
+		Some(from_utf8(bytes))
+	}
+}
+
+/// Reads the whole stream into a `SharedString`.
+///
+/// The read bytes are not copied, splitting the result will share them.
+///
+/// Only available with the `tokio` feature.
+///
+/// ## Errors
+///
+/// Returns an error with the kind `InvalidData` if the stream is not valid
+/// UTF-8.
+///
+/// ## Example
+///
+/// ```
+/// # use shared_string::SharedString;
+/// use shared_string::io::read_to_shared_string;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap()
+/// # .block_on(async {
+/// let mut reader: &[u8] = b"foo\nbar";
+/// let s: SharedString = read_to_shared_string(&mut reader).await.unwrap();
+///
+/// assert_eq!(s.lines().count(), 2);
+/// # });
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn read_to_shared_string<T, R>(
+	reader: &mut T
+) -> io::Result<SharedGenString<R>>
+where
+	T: AsyncRead + Unpin,
+	R: RefCounter {
+	let mut bytes = Vec::new();
+	reader.read_to_end(&mut bytes).await?;
+	from_utf8(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::{SharedString, SharedSyncString};
+
+	use std::io::BufReader;
+
+	#[test]
+	fn lines_from_buf_reader() {
+		let data: &[u8] = b"foo\nb\xc3\xa4r\r\n\nbaz\r";
+		// a small capacity to make sure lines span multiple reads
+		let reader = BufReader::with_capacity(2, data);
+		let lines: Vec<SharedString> = shared_lines(reader)
+			.collect::<io::Result<_>>()
+			.unwrap();
+
+		assert_eq!(lines, ["foo", "bär", "", "baz\r"]);
+		let std: Vec<_> = data.lines().collect::<io::Result<_>>().unwrap();
+		assert_eq!(lines, std);
+	}
+
+	#[test]
+	fn lines_invalid_utf8() {
+		let data: &[u8] = b"foo\n\xff\nbar";
+		let mut lines = shared_lines::<_, crate::Rc<Box<[u8]>>>(data);
+
+		assert_eq!(lines.next().unwrap().unwrap(), "foo");
+		let e = lines.next().unwrap().unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(lines.next().unwrap().unwrap(), "bar");
+		assert!(lines.next().is_none());
+
+		let mut lines = shared_lines(&b""[..]);
+		let line: Option<io::Result<SharedSyncString>> = lines.next();
+		assert!(line.is_none());
+	}
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tokio_tests {
+
+	use super::*;
+	use crate::{SharedString, SharedSyncString};
+
+	use tokio::io::AsyncWriteExt;
+
+	fn block_on<F: std::future::Future>(f: F) -> F::Output {
+		tokio::runtime::Builder::new_current_thread()
+			.build()
+			.unwrap()
+			.block_on(f)
+	}
+
+	#[test]
+	fn read_duplex() {
+		block_on(async {
+			let (mut client, mut server) = tokio::io::duplex(8);
+
+			let write = async {
+				client.write_all(b"GET / HTTP/1.1\r\nHost: a\r\n").await
+					.unwrap();
+				drop(client);
+			};
+			let read = read_to_shared_string(&mut server);
+			let ((), s) = tokio::join!(write, read);
+
+			let s: SharedSyncString = s.unwrap();
+			let lines: Vec<_> = s.lines().collect();
+			assert_eq!(lines, ["GET / HTTP/1.1", "Host: a"]);
+		});
+	}
+
+	#[test]
+	fn read_invalid_utf8() {
+		block_on(async {
+			let mut reader: &[u8] = b"foo\xff";
+			let res: io::Result<SharedString> =
+				read_to_shared_string(&mut reader).await;
+			assert_eq!(res.unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+			let mut reader: &[u8] = b"";
+			let s: SharedString = read_to_shared_string(&mut reader).await
+				.unwrap();
+			assert!(s.is_empty());
+		});
+	}
+}
@@ -3,6 +3,116 @@
 
 use crate::{SharedGenString, RefCounter};
 
+use core::str;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use alloc::vec::Vec;
+
+#[cfg(feature = "unicode")]
+use unicode_segmentation::UnicodeSegmentation;
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+// returns the index of the first byte
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn find_byte(byte: u8, slice: &[u8]) -> Option<usize> {
+	memchr::memchr(byte, slice)
+}
+
+// returns the index of the first byte
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn find_byte(byte: u8, slice: &[u8]) -> Option<usize> {
+	slice.iter().position(|&b| b == byte)
+}
+
+// returns the index of the last byte
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn rfind_byte(byte: u8, slice: &[u8]) -> Option<usize> {
+	memchr::memrchr(byte, slice)
+}
+
+// returns the index of the last byte
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn rfind_byte(byte: u8, slice: &[u8]) -> Option<usize> {
+	slice.iter().rposition(|&b| b == byte)
+}
+
+// returns how many times byte appears in slice
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn count_byte(byte: u8, slice: &[u8]) -> usize {
+	memchr::memchr_iter(byte, slice).count()
+}
+
+// returns how many times byte appears in slice
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn count_byte(byte: u8, slice: &[u8]) -> usize {
+	slice.iter().filter(|&&b| b == byte).count()
+}
+
+// returns the index of the first byte contained in set
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn find_any_byte(set: &[u8], slice: &[u8]) -> Option<usize> {
+	match *set {
+		[] => None,
+		[a] => memchr::memchr(a, slice),
+		[a, b] => memchr::memchr2(a, b, slice),
+		[a, b, c] => memchr::memchr3(a, b, c, slice),
+		_ => slice.iter().position(|b| set.contains(b))
+	}
+}
+
+// returns the index of the first byte contained in set
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn find_any_byte(set: &[u8], slice: &[u8]) -> Option<usize> {
+	slice.iter().position(|b| set.contains(b))
+}
+
+// returns the closest char boundary less than or equal to index
+#[inline]
+pub(crate) fn floor_char_boundary(s: &str, index: usize) -> usize {
+	if index >= s.len() {
+		return s.len()
+	}
+
+	let mut at = index;
+	while !s.is_char_boundary(at) {
+		at -= 1;
+	}
+	at
+}
+
+// returns the closest char boundary greater than or equal to index
+#[inline]
+pub(crate) fn ceil_char_boundary(s: &str, index: usize) -> usize {
+	if index >= s.len() {
+		return s.len()
+	}
+
+	let mut at = index;
+	while !s.is_char_boundary(at) {
+		at += 1;
+	}
+	at
+}
+
+// usize::div_ceil requires Rust 1.73
+#[inline]
+pub(crate) fn div_ceil(a: usize, b: usize) -> usize {
+	if a == 0 {
+		0
+	} else {
+		(a - 1) / b + 1
+	}
+}
+
 /// A Split iterator returned by
 /// [split](../struct.SharedGenString.html#method.split).
 #[derive(Debug, Clone)]
@@ -29,11 +139,15 @@ where R: RefCounter {
 	// returns index of new byte or self.len
 	#[inline]
 	fn find_next(&self) -> usize {
-		self.remaning_slice()
-			.iter()
-			.position(|b| b == &self.byte)
+		find_byte(self.byte, self.remaning_slice())
 			.unwrap_or(self.len)
 	}
+
+	// returns index of the last byte before end or None
+	#[inline]
+	fn find_prev(&self, end: usize) -> Option<usize> {
+		rfind_byte(self.byte, &self.remaning_slice()[..end])
+	}
 }
 
 impl<R> Iterator for Split<R>
@@ -57,21 +171,267 @@ where R: RefCounter {
 			self.bytes.clone()
 		))
 	}
+
+	// every segment contains at least one byte
+	// either the segment itself or the delimiter
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len.min(1), Some(self.len))
+	}
 }
 
-/// A Lines iterator returned by
-/// [lines](../struct.SharedGenString.html#method.lines).
+impl<R> FusedIterator for Split<R>
+where R: RefCounter {}
+
+impl<R> DoubleEndedIterator for Split<R>
+where R: RefCounter {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.len == 0 {
+			return None
+		}
+
+		// a trailing byte does not produce an empty segment
+		let mut end = self.len;
+		if self.remaning_slice()[end - 1] == self.byte {
+			end -= 1;
+		}
+
+		let (at, n_len) = match self.find_prev(end) {
+			Some(at) => (at + 1, at + 1),
+			None => (0, 0)
+		};
+
+		self.len = n_len;
+		Some(SharedGenString::new_raw(
+			self.start + at,
+			end - at,
+			self.bytes.clone()
+		))
+	}
+}
+
+/// A SplitIndices iterator returned by
+/// [split_indices](../struct.SharedGenString.html#method.split_indices).
 #[derive(Debug, Clone)]
-pub struct Lines<R> {
+pub struct SplitIndices<R> {
+	inner: Split<R>,
+	// start of the string split_indices was called on
+	offset: usize
+}
+
+impl<R> SplitIndices<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R, byte: u8) -> Self {
+		Self {
+			inner: Split::new(start, len, bytes, byte),
+			offset: start
+		}
+	}
+}
+
+impl<R> Iterator for SplitIndices<R>
+where R: RefCounter {
+	type Item = (usize, SharedGenString<R>);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let segment = self.inner.next()?;
+		Some((segment.start - self.offset, segment))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<R> DoubleEndedIterator for SplitIndices<R>
+where R: RefCounter {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let segment = self.inner.next_back()?;
+		Some((segment.start - self.offset, segment))
+	}
+}
+
+impl<R> FusedIterator for SplitIndices<R>
+where R: RefCounter {}
+
+/// A SplitN iterator returned by
+/// [splitn](../struct.SharedGenString.html#method.splitn).
+#[derive(Debug, Clone)]
+pub struct SplitN<R> {
+	inner: Split<R>,
+	n: usize
+}
+
+impl<R> SplitN<R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		byte: u8,
+		n: usize
+	) -> Self {
+		Self {
+			inner: Split::new(start, len, bytes, byte),
+			n
+		}
+	}
+}
+
+impl<R> Iterator for SplitN<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match self.n {
+			0 => None,
+			1 => {
+				self.n = 0;
+				if self.inner.len == 0 {
+					return None
+				}
+
+				// return the remaining bytes
+				let len = self.inner.len;
+				self.inner.len = 0;
+				Some(SharedGenString::new_raw(
+					self.inner.start,
+					len,
+					self.inner.bytes.clone()
+				))
+			},
+			_ => {
+				self.n -= 1;
+				self.inner.next()
+			}
+		}
+	}
+}
+
+impl<R> FusedIterator for SplitN<R>
+where R: RefCounter {}
+
+/// A RSplit iterator returned by
+/// [rsplit](../struct.SharedGenString.html#method.rsplit).
+#[derive(Debug, Clone)]
+pub struct RSplit<R> {
+	inner: Split<R>
+}
+
+impl<R> RSplit<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R, byte: u8) -> Self {
+		Self {
+			inner: Split::new(start, len, bytes, byte)
+		}
+	}
+}
+
+impl<R> Iterator for RSplit<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next_back()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<R> DoubleEndedIterator for RSplit<R>
+where R: RefCounter {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.inner.next()
+	}
+}
+
+impl<R> FusedIterator for RSplit<R>
+where R: RefCounter {}
+
+/// A RSplitN iterator returned by
+/// [rsplitn](../struct.SharedGenString.html#method.rsplitn).
+#[derive(Debug, Clone)]
+pub struct RSplitN<R> {
+	inner: Split<R>,
+	n: usize,
+	// if a segment was already returned
+	started: bool
+}
+
+impl<R> RSplitN<R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		byte: u8,
+		n: usize
+	) -> Self {
+		Self {
+			inner: Split::new(start, len, bytes, byte),
+			n,
+			started: false
+		}
+	}
+}
+
+impl<R> Iterator for RSplitN<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match self.n {
+			0 => None,
+			1 => {
+				self.n = 0;
+				if self.inner.len == 0 {
+					return None
+				}
+
+				// return the remaining bytes
+				// without the byte which separated the last segment
+				let len = self.inner.len - self.started as usize;
+				self.inner.len = 0;
+				Some(SharedGenString::new_raw(
+					self.inner.start,
+					len,
+					self.inner.bytes.clone()
+				))
+			},
+			_ => {
+				self.n -= 1;
+				self.started = true;
+				self.inner.next_back()
+			}
+		}
+	}
+}
+
+impl<R> FusedIterator for RSplitN<R>
+where R: RefCounter {}
+
+/// A SplitInclusive iterator returned by
+/// [split_inclusive](../struct.SharedGenString.html#method.split_inclusive).
+#[derive(Debug, Clone)]
+pub struct SplitInclusive<R> {
 	start: usize,
 	len: usize,
-	bytes: R
+	bytes: R,
+	byte: u8
 }
 
-impl<R> Lines<R>
+impl<R> SplitInclusive<R>
 where R: RefCounter {
-	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
-		Self { start, len, bytes }
+	pub(crate) fn new(start: usize, len: usize, bytes: R, byte: u8) -> Self {
+		Self { start, len, bytes, byte }
 	}
 
 	#[inline]
@@ -80,18 +440,81 @@ where R: RefCounter {
 		let range = self.start..(self.start + self.len);
 		unsafe { self.bytes.get_unchecked(range) }
 	}
+}
 
-	// returns index of new byte or self.len
+impl<R> Iterator for SplitInclusive<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.len == 0 {
+			return None
+		}
+
+		// include the byte
+		let n_len = find_byte(self.byte, self.remaning_slice())
+			.map(|at| at + 1)
+			.unwrap_or(self.len);
+
+		let n_start = self.start;
+		self.start += n_len;
+		self.len -= n_len;
+		Some(SharedGenString::new_raw(
+			n_start,
+			n_len,
+			self.bytes.clone()
+		))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len.min(1), Some(self.len))
+	}
+}
+
+impl<R> FusedIterator for SplitInclusive<R>
+where R: RefCounter {}
+
+/// A SplitStr iterator returned by
+/// [split_str](../struct.SharedGenString.html#method.split_str).
+#[derive(Debug, Clone)]
+pub struct SplitStr<'a, R> {
+	start: usize,
+	len: usize,
+	bytes: R,
+	pattern: &'a str
+}
+
+impl<'a, R> SplitStr<'a, R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		pattern: &'a str
+	) -> Self {
+		assert!(!pattern.is_empty(), "pattern cannot be empty");
+		Self { start, len, bytes, pattern }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start is always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+
+	// returns index of the next pattern or self.len
 	#[inline]
 	fn find_next(&self) -> usize {
-		self.remaning_slice()
-			.iter()
-			.position(|&b| b == b'\n')
+		self.remaning_str()
+			.find(self.pattern)
 			.unwrap_or(self.len)
 	}
 }
 
-impl<R> Iterator for Lines<R>
+impl<'a, R> Iterator for SplitStr<'a, R>
 where R: RefCounter {
 	type Item = SharedGenString<R>;
 
@@ -100,23 +523,841 @@ where R: RefCounter {
 			return None
 		}
 
-		let mut at = self.find_next();
-		// + 1 for skipping \n
-		let newline_at = at + 1; // could be out-of-bound
+		let at = self.find_next();
+		let n_at = at + self.pattern.len(); // might out-of-bound
 
 		let n_start = self.start;
-		self.start += newline_at;
-		self.len = self.len.saturating_sub(newline_at);
+		self.start += n_at;
+		self.len = self.len.saturating_sub(n_at);
+		Some(SharedGenString::new_raw(
+			n_start,
+			at,
+			self.bytes.clone()
+		))
+	}
+}
 
-		// check if should do at - 1 (to remove \r)
-		if at >= 1 && self.bytes[n_start + at - 1] == b'\r' {
-			at -= 1;
+impl<'a, R> FusedIterator for SplitStr<'a, R>
+where R: RefCounter {}
+
+/// A SplitAny iterator returned by
+/// [split_any](../struct.SharedGenString.html#method.split_any).
+#[derive(Debug, Clone)]
+pub struct SplitAny<'a, R> {
+	start: usize,
+	len: usize,
+	bytes: R,
+	set: &'a [u8]
+}
+
+impl<'a, R> SplitAny<'a, R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		set: &'a [u8]
+	) -> Self {
+		Self { start, len, bytes, set }
+	}
+
+	#[inline]
+	fn remaning_slice(&self) -> &[u8] {
+		// Safe because only we control start and len
+		let range = self.start..(self.start + self.len);
+		unsafe { self.bytes.get_unchecked(range) }
+	}
+
+	// returns index of the next byte contained in the set or self.len
+	#[inline]
+	fn find_next(&self) -> usize {
+		find_any_byte(self.set, self.remaning_slice())
+			.unwrap_or(self.len)
+	}
+}
+
+impl<'a, R> Iterator for SplitAny<'a, R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.len == 0 {
+			return None
 		}
 
+		let at = self.find_next();
+		let n_at = at + 1; // might out-of-bound
+
+		let n_start = self.start;
+		self.start += n_at;
+		self.len = self.len.saturating_sub(n_at);
 		Some(SharedGenString::new_raw(
 			n_start,
 			at,
 			self.bytes.clone()
 		))
 	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len.min(1), Some(self.len))
+	}
+}
+
+impl<'a, R> FusedIterator for SplitAny<'a, R>
+where R: RefCounter {}
+
+/// A SplitWhitespace iterator returned by
+/// [split_whitespace](../struct.SharedGenString.html#method.split_whitespace).
+#[derive(Debug, Clone)]
+pub struct SplitWhitespace<R> {
+	start: usize,
+	len: usize,
+	bytes: R
 }
+
+impl<R> SplitWhitespace<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self { start, len, bytes }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start is always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+}
+
+impl<R> Iterator for SplitWhitespace<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let remaning = self.remaning_str();
+		let trimmed = remaning.trim_start();
+		if trimmed.is_empty() {
+			self.len = 0;
+			return None
+		}
+
+		let skipped = remaning.len() - trimmed.len();
+		let at = trimmed.find(char::is_whitespace)
+			.unwrap_or(trimmed.len());
+
+		let n_start = self.start + skipped;
+		self.start = n_start + at;
+		self.len -= skipped + at;
+		Some(SharedGenString::new_raw(
+			n_start,
+			at,
+			self.bytes.clone()
+		))
+	}
+}
+
+impl<R> FusedIterator for SplitWhitespace<R>
+where R: RefCounter {}
+
+/// A Lines iterator returned by
+/// [lines](../struct.SharedGenString.html#method.lines).
+#[derive(Debug, Clone)]
+pub struct Lines<R> {
+	start: usize,
+	len: usize,
+	bytes: R
+}
+
+impl<R> Lines<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self { start, len, bytes }
+	}
+
+	#[inline]
+	fn remaning_slice(&self) -> &[u8] {
+		// Safe because only we control start and len
+		let range = self.start..(self.start + self.len);
+		unsafe { self.bytes.get_unchecked(range) }
+	}
+
+	// returns index of new byte or self.len
+	#[inline]
+	fn find_next(&self) -> usize {
+		find_byte(b'\n', self.remaning_slice())
+			.unwrap_or(self.len)
+	}
+}
+
+impl<R> Iterator for Lines<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.len == 0 {
+			return None
+		}
+
+		let mut at = self.find_next();
+		let found_newline = at < self.len;
+		// + 1 for skipping \n
+		let newline_at = at + 1; // could be out-of-bound
+
+		let n_start = self.start;
+		self.start += newline_at;
+		self.len = self.len.saturating_sub(newline_at);
+
+		// check if should do at - 1 (to remove \r)
+		// a \r is only removed if it is followed by a \n
+		if found_newline && at >= 1 && self.bytes[n_start + at - 1] == b'\r' {
+			at -= 1;
+		}
+
+		Some(SharedGenString::new_raw(
+			n_start,
+			at,
+			self.bytes.clone()
+		))
+	}
+
+	// every segment contains at least one byte
+	// either the segment itself or the delimiter
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len.min(1), Some(self.len))
+	}
+}
+
+impl<R> FusedIterator for Lines<R>
+where R: RefCounter {}
+
+/// A LinesWithTerminators iterator returned by
+/// [lines_with_terminators](../struct.SharedGenString.html#method.lines_with_terminators).
+#[derive(Debug, Clone)]
+pub struct LinesWithTerminators<R> {
+	inner: SplitInclusive<R>
+}
+
+impl<R> LinesWithTerminators<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self {
+			inner: SplitInclusive::new(start, len, bytes, b'\n')
+		}
+	}
+}
+
+impl<R> Iterator for LinesWithTerminators<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	// \r\n is always included since \r comes before \n
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<R> FusedIterator for LinesWithTerminators<R>
+where R: RefCounter {}
+
+/// A LineRanges iterator returned by
+/// [line_ranges](../struct.SharedGenString.html#method.line_ranges).
+#[derive(Debug, Clone)]
+pub struct LineRanges<R> {
+	inner: Lines<R>,
+	// start of the string line_ranges was called on
+	offset: usize
+}
+
+impl<R> LineRanges<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self {
+			inner: Lines::new(start, len, bytes),
+			offset: start
+		}
+	}
+}
+
+impl<R> Iterator for LineRanges<R>
+where R: RefCounter {
+	type Item = (Range<usize>, SharedGenString<R>);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let line = self.inner.next()?;
+		let start = line.start - self.offset;
+		Some((start..(start + line.len), line))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<R> FusedIterator for LineRanges<R>
+where R: RefCounter {}
+
+/// A LineWindows iterator returned by
+/// [line_windows](../struct.SharedGenString.html#method.line_windows).
+#[derive(Debug, Clone)]
+pub struct LineWindows<R>
+where R: RefCounter {
+	inner: Lines<R>,
+	window: Vec<SharedGenString<R>>,
+	n: usize
+}
+
+impl<R> LineWindows<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R, n: usize) -> Self {
+		assert!(n != 0, "window size cannot be zero");
+		Self {
+			inner: Lines::new(start, len, bytes),
+			window: Vec::with_capacity(n),
+			n
+		}
+	}
+}
+
+impl<R> Iterator for LineWindows<R>
+where R: RefCounter {
+	type Item = Vec<SharedGenString<R>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.window.len() == self.n {
+			self.window.remove(0);
+		}
+
+		while self.window.len() < self.n {
+			self.window.push(self.inner.next()?);
+		}
+
+		Some(self.window.clone())
+	}
+}
+
+impl<R> FusedIterator for LineWindows<R>
+where R: RefCounter {}
+
+/// A MatchIndices iterator returned by
+/// [match_indices](../struct.SharedGenString.html#method.match_indices).
+#[derive(Debug, Clone)]
+pub struct MatchIndices<'a, R> {
+	// the start of the SharedString
+	offset: usize,
+	start: usize,
+	len: usize,
+	bytes: R,
+	pattern: &'a str
+}
+
+impl<'a, R> MatchIndices<'a, R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		pattern: &'a str
+	) -> Self {
+		assert!(!pattern.is_empty(), "pattern cannot be empty");
+		Self { offset: start, start, len, bytes, pattern }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start is always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+}
+
+impl<'a, R> Iterator for MatchIndices<'a, R>
+where R: RefCounter {
+	type Item = (usize, SharedGenString<R>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let at = match self.remaning_str().find(self.pattern) {
+			Some(at) => at,
+			None => {
+				self.len = 0;
+				return None
+			}
+		};
+
+		let n_start = self.start + at;
+		let n_at = at + self.pattern.len();
+		self.start += n_at;
+		self.len -= n_at;
+		Some((
+			n_start - self.offset,
+			SharedGenString::new_raw(
+				n_start,
+				self.pattern.len(),
+				self.bytes.clone()
+			)
+		))
+	}
+}
+
+impl<'a, R> FusedIterator for MatchIndices<'a, R>
+where R: RefCounter {}
+
+/// A Matches iterator returned by
+/// [matches](../struct.SharedGenString.html#method.matches).
+#[derive(Debug, Clone)]
+pub struct Matches<'a, R> {
+	inner: MatchIndices<'a, R>
+}
+
+impl<'a, R> Matches<'a, R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		pattern: &'a str
+	) -> Self {
+		Self { inner: MatchIndices::new(start, len, bytes, pattern) }
+	}
+}
+
+impl<'a, R> Iterator for Matches<'a, R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next().map(|(_, m)| m)
+	}
+}
+
+impl<'a, R> FusedIterator for Matches<'a, R>
+where R: RefCounter {}
+
+/// A Chunks iterator returned by
+/// [chunks](../struct.SharedGenString.html#method.chunks).
+#[derive(Debug, Clone)]
+pub struct Chunks<R> {
+	start: usize,
+	len: usize,
+	bytes: R,
+	size: usize
+}
+
+impl<R> Chunks<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R, size: usize) -> Self {
+		assert!(size != 0, "chunk size cannot be zero");
+		Self { start, len, bytes, size }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start and len are always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+
+	// returns the len of the next chunk
+	fn find_next(&self) -> usize {
+		let s = self.remaning_str();
+		let mut at = floor_char_boundary(s, self.size);
+
+		// the first char is longer than size
+		if at == 0 {
+			at = s.chars().next().map(char::len_utf8).unwrap_or(0);
+		}
+
+		at
+	}
+}
+
+impl<R> Iterator for Chunks<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.len == 0 {
+			return None
+		}
+
+		let at = self.find_next();
+
+		let n_start = self.start;
+		self.start += at;
+		self.len -= at;
+		Some(SharedGenString::new_raw(n_start, at, self.bytes.clone()))
+	}
+
+	// a chunk contains between one char and size bytes
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(div_ceil(self.len, self.size), Some(self.len))
+	}
+}
+
+impl<R> FusedIterator for Chunks<R>
+where R: RefCounter {}
+
+/// A RChunks iterator returned by
+/// [rchunks](../struct.SharedGenString.html#method.rchunks).
+#[derive(Debug, Clone)]
+pub struct RChunks<R> {
+	start: usize,
+	len: usize,
+	bytes: R,
+	size: usize
+}
+
+impl<R> RChunks<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R, size: usize) -> Self {
+		assert!(size != 0, "chunk size cannot be zero");
+		Self { start, len, bytes, size }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start and len are always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+
+	// returns the index where the next chunk starts
+	fn find_next(&self) -> usize {
+		let s = self.remaning_str();
+		let mut at = ceil_char_boundary(s, self.len.saturating_sub(self.size));
+
+		// the last char is longer than size
+		if at == self.len {
+			at -= s.chars().next_back().map(char::len_utf8).unwrap_or(0);
+		}
+
+		at
+	}
+}
+
+impl<R> Iterator for RChunks<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.len == 0 {
+			return None
+		}
+
+		let at = self.find_next();
+
+		let n_len = self.len - at;
+		self.len = at;
+		Some(SharedGenString::new_raw(
+			self.start + at,
+			n_len,
+			self.bytes.clone()
+		))
+	}
+
+	// a chunk contains between one char and size bytes
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(div_ceil(self.len, self.size), Some(self.len))
+	}
+}
+
+impl<R> FusedIterator for RChunks<R>
+where R: RefCounter {}
+
+/// An IntoChars iterator returned by
+/// [into_iter](../struct.SharedGenString.html#impl-IntoIterator).
+#[derive(Debug, Clone)]
+pub struct IntoChars<R> {
+	start: usize,
+	len: usize,
+	bytes: R
+}
+
+impl<R> IntoChars<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self { start, len, bytes }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start and len are always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+}
+
+impl<R> Iterator for IntoChars<R>
+where R: RefCounter {
+	type Item = char;
+
+	#[inline]
+	fn next(&mut self) -> Option<char> {
+		let c = self.remaning_str().chars().next()?;
+		self.start += c.len_utf8();
+		self.len -= c.len_utf8();
+		Some(c)
+	}
+
+	// a char contains between 1 and 4 bytes
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(div_ceil(self.len, 4), Some(self.len))
+	}
+}
+
+impl<R> DoubleEndedIterator for IntoChars<R>
+where R: RefCounter {
+	#[inline]
+	fn next_back(&mut self) -> Option<char> {
+		let c = self.remaning_str().chars().next_back()?;
+		self.len -= c.len_utf8();
+		Some(c)
+	}
+}
+
+impl<R> FusedIterator for IntoChars<R>
+where R: RefCounter {}
+
+/// An IntoBytes iterator returned by
+/// [into_bytes_iter](../struct.SharedGenString.html#method.into_bytes_iter).
+#[derive(Debug, Clone)]
+pub struct IntoBytes<R> {
+	start: usize,
+	len: usize,
+	bytes: R
+}
+
+impl<R> IntoBytes<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self { start, len, bytes }
+	}
+}
+
+impl<R> Iterator for IntoBytes<R>
+where R: RefCounter {
+	type Item = u8;
+
+	#[inline]
+	fn next(&mut self) -> Option<u8> {
+		if self.len == 0 {
+			return None
+		}
+
+		let b = self.bytes[self.start];
+		self.start += 1;
+		self.len -= 1;
+		Some(b)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len, Some(self.len))
+	}
+}
+
+impl<R> DoubleEndedIterator for IntoBytes<R>
+where R: RefCounter {
+	#[inline]
+	fn next_back(&mut self) -> Option<u8> {
+		if self.len == 0 {
+			return None
+		}
+
+		self.len -= 1;
+		Some(self.bytes[self.start + self.len])
+	}
+}
+
+impl<R> ExactSizeIterator for IntoBytes<R>
+where R: RefCounter {}
+
+impl<R> FusedIterator for IntoBytes<R>
+where R: RefCounter {}
+
+/// A RegexSplit iterator returned by
+/// [split_regex](../struct.SharedGenString.html#method.split_regex).
+///
+/// Only available with the `regex` feature.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct RegexSplit<'a, R> {
+	start: usize,
+	len: usize,
+	bytes: R,
+	re: &'a Regex,
+	// start of the next segment
+	last: usize,
+	// where the next search starts
+	search_at: usize,
+	// end of the last match
+	last_match: Option<usize>
+}
+
+#[cfg(feature = "regex")]
+impl<'a, R> RegexSplit<'a, R>
+where R: RefCounter {
+	pub(crate) fn new(
+		start: usize,
+		len: usize,
+		bytes: R,
+		re: &'a Regex
+	) -> Self {
+		Self {
+			start, len, bytes, re,
+			last: 0,
+			search_at: 0,
+			last_match: None
+		}
+	}
+
+	#[inline]
+	fn full_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start and len are always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+
+	// returns the start and end of the next match
+	fn find_next(&mut self) -> Option<(usize, usize)> {
+		loop {
+			if self.search_at > self.len {
+				return None
+			}
+
+			let m = self.re.find_at(self.full_str(), self.search_at)?;
+			let (start, end) = (m.start(), m.end());
+
+			if start == end {
+				// make progress after an empty match
+				self.search_at = self.full_str()[end..].chars().next()
+					.map(|c| end + c.len_utf8())
+					.unwrap_or(self.len + 1);
+
+				// don't accept an empty match directly after a match
+				if Some(end) == self.last_match {
+					continue
+				}
+			} else {
+				self.search_at = end;
+			}
+
+			self.last_match = Some(end);
+			return Some((start, end))
+		}
+	}
+}
+
+#[cfg(feature = "regex")]
+impl<'a, R> Iterator for RegexSplit<'a, R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (at, n_last) = match self.find_next() {
+			Some(m) => m,
+			// a trailing match does not produce an empty segment
+			None if self.last >= self.len => return None,
+			None => (self.len, self.len)
+		};
+
+		let n_start = self.last;
+		self.last = n_last;
+		Some(SharedGenString::new_raw(
+			self.start + n_start,
+			at - n_start,
+			self.bytes.clone()
+		))
+	}
+}
+
+#[cfg(feature = "regex")]
+impl<'a, R> FusedIterator for RegexSplit<'a, R>
+where R: RefCounter {}
+
+/// A Graphemes iterator returned by
+/// [graphemes](../struct.SharedGenString.html#method.graphemes).
+///
+/// Only available with the `unicode` feature.
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone)]
+pub struct Graphemes<R> {
+	start: usize,
+	len: usize,
+	bytes: R
+}
+
+#[cfg(feature = "unicode")]
+impl<R> Graphemes<R>
+where R: RefCounter {
+	pub(crate) fn new(start: usize, len: usize, bytes: R) -> Self {
+		Self { start, len, bytes }
+	}
+
+	#[inline]
+	fn remaning_str(&self) -> &str {
+		// Safe because only we control start and len
+		// and start and len are always at a char boundary
+		let range = self.start..(self.start + self.len);
+		unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+	}
+}
+
+#[cfg(feature = "unicode")]
+impl<R> Iterator for Graphemes<R>
+where R: RefCounter {
+	type Item = SharedGenString<R>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let len = self.remaning_str().graphemes(true).next()?.len();
+
+		let n_start = self.start;
+		self.start += len;
+		self.len -= len;
+		Some(SharedGenString::new_raw(n_start, len, self.bytes.clone()))
+	}
+
+	// every grapheme contains at least one byte
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len.min(1), Some(self.len))
+	}
+}
+
+#[cfg(feature = "unicode")]
+impl<R> DoubleEndedIterator for Graphemes<R>
+where R: RefCounter {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let len = self.remaning_str().graphemes(true).next_back()?.len();
+
+		self.len -= len;
+		Some(SharedGenString::new_raw(
+			self.start + self.len,
+			len,
+			self.bytes.clone()
+		))
+	}
+}
+
+#[cfg(feature = "unicode")]
+impl<R> FusedIterator for Graphemes<R>
+where R: RefCounter {}
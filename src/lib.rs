@@ -9,8 +9,8 @@
 //!
 //! First try to store references, for example `&str` which is more efficient.
 //!
-//! At the moment if you create a `SharedString` the underlying bytes cannot be
-//! mutated anymore.
+//! The underlying bytes can only be mutated with `to_mut` which copies them
+//! if they are shared with another `SharedString`.
 //!
 //! ## Example
 //!
@@ -47,15 +47,80 @@
 //!
 //! `SharedString` can increase the perfomance in situations such as the example
 //! above by over 30%. See `benches/*` for benchmarks.
+//!
+//! ## Features
+//!
+//! - `std` (default): Implementations which require `std`, without it only
+//!   `alloc` is needed.
+//! - `memchr` (default): Uses `memchr` to find bytes faster.
+//! - `serde`: Implements `Serialize` and `Deserialize`, the [serde_bytes]
+//!   module allows to encode a `SharedString` as bytes.
+//! - `arbitrary`: Implements `Arbitrary` to use `SharedString` in fuzz
+//!   targets.
+//! - `proptest`: Adds the [strategy] module and implements proptest's
+//!   `Arbitrary`.
+//! - `http`: Implements `TryFrom<SharedString>` for `HeaderName` and
+//!   `HeaderValue`.
+//! - `regex`: Adds [split_regex](struct.SharedGenString.html#method.split_regex).
+//! - `rkyv`: Implements `Archive`, `Serialize` and `Deserialize`, a
+//!   `SharedString` is archived as an `ArchivedString`.
+//! - `tokio`: Adds [read_to_shared_string](io/fn.read_to_shared_string.html)
+//!   to read from an `AsyncRead`.
+//! - `unicode`: Adds [graphemes](struct.SharedGenString.html#method.graphemes)
+//!   using `unicode-segmentation`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#![allow(clippy::tabs_in_doc_comments)]
+
+extern crate alloc;
 
 pub mod iter;
-use iter::{Split, Lines};
+pub mod pattern;
+mod builder;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+pub mod serde_bytes;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "http")]
+mod http_impl;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "std")]
+pub mod io;
+
+use iter::{
+	Split, SplitIndices, SplitN, RSplit, RSplitN, SplitInclusive, SplitStr, SplitAny,
+	SplitWhitespace, Lines, LinesWithTerminators, LineRanges, LineWindows,
+	MatchIndices, Matches, Chunks, RChunks, IntoChars, IntoBytes
+};
+#[cfg(feature = "unicode")]
+use iter::Graphemes;
+#[cfg(feature = "regex")]
+use iter::RegexSplit;
+#[cfg(feature = "regex")]
+use regex::Regex;
 
-use std::{ops, str, cmp, fmt, hash, borrow};
+use pattern::Pattern;
+pub use builder::SharedStringBuilder;
+
+use core::{ops, str, cmp, fmt, hash, borrow, mem, ptr};
 use ops::Bound;
-use std::rc::Rc;
-use std::sync::Arc;
-use std::string::FromUtf8Error;
+use core::str::Utf8Error;
+use core::convert::{TryFrom, Infallible};
+use core::iter::FromIterator;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString, FromUtf8Error, FromUtf16Error};
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use std::path::Path;
 
 /// A `SharedString`, generic over its reference counter.
 ///
@@ -93,30 +158,89 @@ pub type SharedString = SharedGenString<Rc<Box<[u8]>>>;
 /// Use `SharedSyncString` if you need to pass it between threads
 pub type SharedSyncString = SharedGenString<Arc<Box<[u8]>>>;
 
+mod sealed {
+	pub trait Sealed {}
+}
+
 /// A trait to allow `SharedString` to be generic over any reference counter.
 ///
 /// Implemented for `Rc` and `Arc`.
 ///
 /// Requires the traits `Clone` + `Sized` +
 /// `Deref<Box<[u8]>>` + `From<Box<[u8]>>`
-pub trait RefCounter: Clone + Sized + ops::Deref<Target = Box<[u8]>> + From<Box<[u8]>> {
+///
+/// ## Note
+///
+/// Since version 0.2 this trait is sealed and can't be implemented outside
+/// of this crate, so methods can be added without breaking changes.
+pub trait RefCounter: sealed::Sealed + Clone + Sized + ops::Deref<Target = Box<[u8]>> + From<Box<[u8]>> {
 	fn try_unwrap(self) -> Result<Box<[u8]>, Self>;
+
+	fn strong_count(&self) -> usize;
+
+	fn get_mut(&mut self) -> Option<&mut Box<[u8]>>;
 }
 
+impl sealed::Sealed for Rc<Box<[u8]>> {}
+
 impl RefCounter for Rc<Box<[u8]>> {
 	#[inline]
 	fn try_unwrap(self) -> Result<Box<[u8]>, Self> {
 		Rc::try_unwrap(self)
 	}
+
+	#[inline]
+	fn strong_count(&self) -> usize {
+		Rc::strong_count(self)
+	}
+
+	#[inline]
+	fn get_mut(&mut self) -> Option<&mut Box<[u8]>> {
+		Rc::get_mut(self)
+	}
 }
 
+impl sealed::Sealed for Arc<Box<[u8]>> {}
+
 impl RefCounter for Arc<Box<[u8]>> {
 	#[inline]
 	fn try_unwrap(self) -> Result<Box<[u8]>, Self> {
 		Arc::try_unwrap(self)
 	}
+
+	#[inline]
+	fn strong_count(&self) -> usize {
+		Arc::strong_count(self)
+	}
+
+	#[inline]
+	fn get_mut(&mut self) -> Option<&mut Box<[u8]>> {
+		Arc::get_mut(self)
+	}
+}
+
+/// The error returned by
+/// [try_split_off](struct.SharedGenString.html#method.try_split_off).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitError {
+	/// The index is greater than the length.
+	OutOfBounds,
+	/// The index does not lie on a char boundary.
+	NotCharBoundary
+}
+
+impl fmt::Display for SplitError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::OutOfBounds => f.write_str("index out of bounds"),
+			Self::NotCharBoundary => f.write_str("index not at a char boundary")
+		}
+	}
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for SplitError {}
+
 impl<R> SharedGenString<R>
 where R: RefCounter {
 
@@ -152,6 +276,10 @@ where R: RefCounter {
 	/// every bytes is valid UTF-8.
 	///
 	/// Safe version [from_utf8](#method.from_utf8)
+	///
+	/// ## Safety
+	///
+	/// The bytes passed in must be valid UTF-8.
 	#[inline]
 	pub unsafe fn from_utf8_unchecked(vec: Vec<u8>) -> Self {
 		Self {
@@ -161,10 +289,102 @@ where R: RefCounter {
 		}
 	}
 
+	/// Converts a vector of bytes to a `SharedString`, replacing invalid
+	/// UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+	///
+	/// Behaves the same way as [String::from_utf8_lossy](https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8_lossy).
+	///
+	/// If the bytes are valid UTF-8 they are reused without a copy.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from_utf8_lossy(b"Hello \xF0\x90\x80World".to_vec());
+	///
+	/// assert_eq!("Hello �World", s);
+	/// ```
+	#[inline]
+	pub fn from_utf8_lossy(vec: Vec<u8>) -> Self {
+		match String::from_utf8_lossy(&vec) {
+			// Safe because the bytes are valid UTF-8
+			Cow::Borrowed(_) => unsafe { Self::from_utf8_unchecked(vec) },
+			Cow::Owned(s) => s.into()
+		}
+	}
+
+	/// Reads everything from `reader` into a `SharedString`.
+	///
+	/// All bytes are stored in a single allocation which gets shared by
+	/// every `SharedString` created from the result, for example with
+	/// [lines](#method.lines) or [split](#method.split).
+	///
+	/// Only available with the `std` feature.
+	///
+	/// ## Errors
+	///
+	/// Returns an error with the kind `InvalidData` if the bytes are not
+	/// valid UTF-8.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let reader: &[u8] = b"foo\nbar";
+	/// let s = SharedString::from_reader_all(reader).unwrap();
+	///
+	/// assert_eq!(s.lines().count(), 2);
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn from_reader_all<T>(mut reader: T) -> std::io::Result<Self>
+	where T: std::io::Read {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes)?;
+		io::from_utf8(bytes)
+	}
+
+	/// Decodes a UTF-16 encoded slice into a `SharedString`, returning an
+	/// error if it contains invalid data.
+	///
+	/// Behaves the same way as [String::from_utf16](https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf16).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let music = [0xD834, 0xDD1E, 0x006d, 0x0075, 0x0073, 0x0069, 0x0063];
+	/// assert_eq!("𝄞music", SharedString::from_utf16(&music).unwrap());
+	///
+	/// let invalid = [0xD834, 0xDD1E, 0x006d, 0x0075, 0xD800, 0x0069, 0x0063];
+	/// assert!(SharedString::from_utf16(&invalid).is_err());
+	/// ```
+	#[inline]
+	pub fn from_utf16(v: &[u16]) -> Result<Self, FromUtf16Error> {
+		String::from_utf16(v).map(Into::into)
+	}
+
+	/// Decodes a UTF-16 encoded slice into a `SharedString`, replacing
+	/// invalid data with `U+FFFD REPLACEMENT CHARACTER`.
+	///
+	/// Behaves the same way as [String::from_utf16_lossy](https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf16_lossy).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let invalid = [0xD834, 0xDD1E, 0x006d, 0x0075, 0xD800, 0x0069, 0x0063];
+	///
+	/// assert_eq!("𝄞mu\u{FFFD}ic", SharedString::from_utf16_lossy(&invalid));
+	/// ```
+	#[inline]
+	pub fn from_utf16_lossy(v: &[u16]) -> Self {
+		String::from_utf16_lossy(v).into()
+	}
+
 	/// Returns a byte slice of the underlying bytes.
 	///
 	/// To get the full bytes from which this `SharedString` was created from
-	/// use [as_bytes_full](#method.as_bytes_full).
+	/// use [as_full_bytes](#method.as_full_bytes).
 	#[inline]
 	pub fn as_bytes(&self) -> &[u8] {
 		let end = self.start + self.len;
@@ -177,11 +397,68 @@ where R: RefCounter {
 
 	/// Return a byte slice of the bytes from which this `SharedString` was
 	/// created.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	/// let bar = foobar.idx(3..);
+	///
+	/// assert_eq!(b"bar", bar.as_bytes());
+	/// assert_eq!(b"foobar", bar.as_full_bytes());
+	/// ```
 	#[inline]
 	pub fn as_full_bytes(&self) -> &[u8] {
 		&self.bytes
 	}
 
+	/// Returns the byte at index `i`.
+	///
+	/// Returns `None` if `i` is out of bounds.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("foo");
+	///
+	/// assert_eq!(Some(b'o'), s.byte(1));
+	/// assert_eq!(None, s.byte(3));
+	/// ```
+	#[inline]
+	pub fn byte(&self, i: usize) -> Option<u8> {
+		self.as_bytes().get(i).copied()
+	}
+
+	/// Returns the first byte or `None` if the string is empty.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// assert_eq!(Some(b'f'), SharedString::from("foo").first_byte());
+	/// assert_eq!(None, SharedString::new().first_byte());
+	/// ```
+	#[inline]
+	pub fn first_byte(&self) -> Option<u8> {
+		self.as_bytes().first().copied()
+	}
+
+	/// Returns the last byte or `None` if the string is empty.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// assert_eq!(Some(b'r'), SharedString::from("bar").last_byte());
+	/// assert_eq!(None, SharedString::new().last_byte());
+	/// ```
+	#[inline]
+	pub fn last_byte(&self) -> Option<u8> {
+		self.as_bytes().last().copied()
+	}
+
 	/// Returns a string slice of the `SharedString`.
 	///
 	/// ## Example
@@ -194,7 +471,7 @@ where R: RefCounter {
 	/// ```
 	#[inline]
 	pub fn as_str(&self) -> &str {
-		&self
+		self
 	}
 
 	/// Return a string slice of the bytes from which this `SharedString` was
@@ -232,21 +509,227 @@ where R: RefCounter {
 		self.len
 	}
 
+	/// Returns the number of chars in this `SharedString`.
+	///
+	/// Unlike [len](#method.len) which returns the number of bytes, this
+	/// counts every Unicode scalar value and is therefore `O(n)`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a好");
+	///
+	/// assert_eq!(4, s.len());
+	/// assert_eq!(2, s.char_len());
+	/// ```
+	#[inline]
+	pub fn char_len(&self) -> usize {
+		self.as_str().chars().count()
+	}
+
+	/// Returns the char starting at the byte index `byte_index`.
+	///
+	/// Returns `None` if `byte_index` is out of bounds or not on a char
+	/// boundary.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("aäb");
+	///
+	/// assert_eq!(Some('ä'), s.char_at(1));
+	/// assert_eq!(None, s.char_at(2));
+	/// assert_eq!(Some('b'), s.char_at(3));
+	/// assert_eq!(None, s.char_at(4));
+	/// ```
+	#[inline]
+	pub fn char_at(&self, byte_index: usize) -> Option<char> {
+		self.as_str().get(byte_index..)?.chars().next()
+	}
+
+	/// Returns the closest char boundary less than or equal to `index`.
+	///
+	/// If `index` is greater than the length, the length is returned. The
+	/// result can always be passed to [idx](#method.idx) or
+	/// [split_off](#method.split_off).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a好b");
+	///
+	/// assert_eq!(1, s.floor_char_boundary(2));
+	/// assert_eq!(4, s.floor_char_boundary(4));
+	/// assert_eq!("a", s.idx(..s.floor_char_boundary(3)));
+	/// ```
+	#[inline]
+	pub fn floor_char_boundary(&self, index: usize) -> usize {
+		iter::floor_char_boundary(self.as_str(), index)
+	}
+
+	/// Returns the closest char boundary greater than or equal to `index`.
+	///
+	/// If `index` is greater than the length, the length is returned. The
+	/// result can always be passed to [idx](#method.idx) or
+	/// [split_off](#method.split_off).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a好b");
+	///
+	/// assert_eq!(4, s.ceil_char_boundary(2));
+	/// assert_eq!(1, s.ceil_char_boundary(1));
+	/// assert_eq!("a好", s.idx(..s.ceil_char_boundary(3)));
+	/// ```
+	#[inline]
+	pub fn ceil_char_boundary(&self, index: usize) -> usize {
+		iter::ceil_char_boundary(self.as_str(), index)
+	}
+
+	/// Returns the `n`th char, counting from zero.
+	///
+	/// This is `O(n)`, like [char_len](#method.char_len).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("aäb");
+	///
+	/// assert_eq!(Some('ä'), s.nth_char(1));
+	/// assert_eq!(Some('b'), s.nth_char(2));
+	/// assert_eq!(None, s.nth_char(3));
+	/// ```
+	#[inline]
+	pub fn nth_char(&self, n: usize) -> Option<char> {
+		self.as_str().chars().nth(n)
+	}
+
+	/// Returns `true` if all chars are ASCII, and `false` otherwise.
+	///
+	/// If this returns `true` every byte index is at a char boundary, so
+	/// [get](#method.get), [idx](#method.idx) or
+	/// [split_off](#method.split_off) only fail if the index is
+	/// out-of-bounds.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("foo bär");
+	///
+	/// assert!(!s.is_ascii());
+	/// assert!(s.idx(..4).is_ascii());
+	/// ```
+	#[inline]
+	pub fn is_ascii(&self) -> bool {
+		self.as_bytes().is_ascii()
+	}
+
 	/// Returns `true` if the length is zero, and `false` otherwise.
 	#[inline]
 	pub fn is_empty(&self) -> bool {
 		self.len == 0
 	}
 
-	// returns new start and end if it is a valid range
-	// will be equal to x..y
-	// valid: start <= end && end <= len
+	/// Truncates this `SharedString`, removing all contents.
+	///
+	/// The reference to the underlying bytes gets dropped.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut foo = SharedString::from("foo");
+	/// foo.clear();
+	///
+	/// assert!(foo.is_empty());
+	/// ```
 	#[inline]
-	fn validate_range<I>(&self, range: I) -> Option<(usize, usize)>
-	where I: ops::RangeBounds<usize> {
+	pub fn clear(&mut self) {
+		*self = Self::new();
+	}
+
+	/// Returns `true` if no other `SharedString` references the underlying
+	/// bytes.
+	///
+	/// If `true` conversions like [into_string](#method.into_string) might
+	/// not need to copy.
+	///
+	/// ## Note
+	///
+	/// With `SharedSyncString` another thread could clone or drop a
+	/// reference at any time, so the result should only be seen as a hint.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut foo = SharedString::from("foobar");
+	/// assert!(foo.is_unique());
+	///
+	/// let bar = foo.split_off(3);
+	/// assert!(!foo.is_unique());
+	///
+	/// drop(bar);
+	/// assert!(foo.is_unique());
+	/// ```
+	#[inline]
+	pub fn is_unique(&self) -> bool {
+		self.ref_count() == 1
+	}
+
+	/// Returns the number of `SharedString`s referencing the underlying
+	/// bytes.
+	///
+	/// The same note as in [is_unique](#method.is_unique) applies.
+	#[inline]
+	pub fn ref_count(&self) -> usize {
+		self.bytes.strong_count()
+	}
+
+	/// Returns the byte offset at which this `SharedString` starts inside
+	/// `parent`.
+	///
+	/// Returns `None` if both don't share the same underlying bytes or if
+	/// `self` is not contained in `parent`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let input = SharedString::from("key: value");
+	/// let value = input.idx(5..);
+	///
+	/// assert_eq!(Some(5), value.offset_in(&input));
+	/// assert_eq!(None, input.offset_in(&value));
+	/// assert_eq!(None, value.offset_in(&SharedString::from("key: value")));
+	/// ```
+	pub fn offset_in(&self, parent: &Self) -> Option<usize> {
+		let same = ptr::eq(
+			self.as_full_bytes() as *const [u8],
+			parent.as_full_bytes() as *const [u8]
+		);
+		let end = self.start + self.len;
+		let parent_end = parent.start + parent.len;
 
-		let len = self.len();
+		if same && self.start >= parent.start && end <= parent_end {
+			Some(self.start - parent.start)
+		} else {
+			None
+		}
+	}
 
+	// returns start and end without validating them
+	// will be equal to x..y
+	#[inline]
+	fn resolve_range<I>(&self, range: I) -> (usize, usize)
+	where I: ops::RangeBounds<usize> {
 		let start = match range.start_bound() {
 			Bound::Included(&i) => i,
 			Bound::Excluded(&i) => i + 1,
@@ -256,10 +739,21 @@ where R: RefCounter {
 		let end = match range.end_bound() {
 			Bound::Included(&i) => i + 1,
 			Bound::Excluded(&i) => i,
-			Bound::Unbounded => len
+			Bound::Unbounded => self.len()
 		};
 
-		if start > end || end > len {
+		(start, end)
+	}
+
+	// returns new start and end if it is a valid range
+	// will be equal to x..y
+	// valid: start <= end && end <= len
+	#[inline]
+	fn validate_range<I>(&self, range: I) -> Option<(usize, usize)>
+	where I: ops::RangeBounds<usize> {
+		let (start, end) = self.resolve_range(range);
+
+		if start > end || end > self.len() {
 			None
 		} else {
 			Some((start, end))
@@ -272,6 +766,9 @@ where R: RefCounter {
 	/// `None` if the range is out-of-bounds or if the start or the end are not
 	/// at a char_boundary.
 	///
+	/// If the string [is_ascii](#method.is_ascii) every index is at a char
+	/// boundary, so only the bounds need to be valid.
+	///
 	/// No allocation is performed.
 	///
 	/// ## Example
@@ -351,9 +848,119 @@ where R: RefCounter {
 		}
 	}
 
-	/// Convert `SharedString` to a `Vec<u8>`.
+	/// Returns a substring of `SharedString` without checking the range.
 	///
-	/// Avoids an allocation if the underlying data is not used by another
+	/// Like [idx](#method.idx) but without any checks, in debug builds the
+	/// range still gets validated.
+	///
+	/// ## Safety
+	///
+	/// The caller must make sure that:
+	/// - the start is not greater than the end
+	/// - the end is not greater than [len](#method.len)
+	/// - the start and the end are at a char boundary
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	/// let at = foobar.find_index("bar").unwrap();
+	///
+	/// // Safe because at was returned by find_index
+	/// let bar = unsafe { foobar.idx_unchecked(at..) };
+	/// assert_eq!("bar", bar);
+	/// ```
+	#[inline]
+	pub unsafe fn idx_unchecked<I>(&self, range: I) -> Self
+	where I: ops::RangeBounds<usize> {
+		let (start, end) = self.resolve_range(range);
+
+		debug_assert!(start <= end, "start is greater than end");
+		debug_assert!(end <= self.len, "range out-of-bounds");
+		debug_assert!(
+			self.is_char_boundary(start) && self.is_char_boundary(end),
+			"range not at a char boundary"
+		);
+
+		Self {
+			start: self.start + start,
+			len: end - start,
+			bytes: self.bytes.clone()
+		}
+	}
+
+	/// Returns a mutable string slice of the `SharedString`.
+	///
+	/// If the underlying bytes are shared with another `SharedString` the
+	/// bytes of this `SharedString` get copied first, so other instances are
+	/// never affected (copy-on-write).
+	///
+	/// To get an owned `String` use [into_string](#method.into_string).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut foo = SharedString::from("foo");
+	/// let clone = foo.clone();
+	///
+	/// foo.to_mut().make_ascii_uppercase();
+	/// assert_eq!(foo, "FOO");
+	/// assert_eq!(clone, "foo");
+	/// ```
+	#[inline]
+	pub fn to_mut(&mut self) -> &mut str {
+		if self.bytes.get_mut().is_none() {
+			let bytes: Box<[u8]> = self.as_bytes().into();
+			self.start = 0;
+			self.bytes = bytes.into();
+		}
+
+		let range = self.start..(self.start + self.len);
+		// unwrap because we made sure we are the only owner
+		let bytes = self.bytes.get_mut().unwrap();
+		// Safe because we control start and end
+		// and know that it is not out-of-bounds
+		// and the bytes are valid UTF-8
+		unsafe {
+			str::from_utf8_unchecked_mut(bytes.get_unchecked_mut(range))
+		}
+	}
+
+	/// Returns a mutable string slice of the `SharedString` if no other
+	/// `SharedString` references the underlying bytes.
+	///
+	/// Returns `None` otherwise, use [to_mut](#method.to_mut) to copy the
+	/// bytes in that case.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut foo = SharedString::from("foo");
+	/// foo.as_str_mut().unwrap().make_ascii_uppercase();
+	/// assert_eq!(foo, "FOO");
+	///
+	/// let clone = foo.clone();
+	/// assert!(foo.as_str_mut().is_none());
+	/// # drop(clone);
+	/// ```
+	#[inline]
+	pub fn as_str_mut(&mut self) -> Option<&mut str> {
+		let range = self.start..(self.start + self.len);
+		let bytes = self.bytes.get_mut()?;
+		// Safe because we control start and end
+		// and know that it is not out-of-bounds
+		// and the bytes are valid UTF-8
+		Some(unsafe {
+			str::from_utf8_unchecked_mut(bytes.get_unchecked_mut(range))
+		})
+	}
+
+	/// Convert `SharedString` to a `Vec<u8>`.
+	///
+	/// Avoids an allocation if the underlying data is not used by another
 	/// instance of `SharedString` and start is at zero.
 	#[inline]
 	pub fn into_bytes(self) -> Vec<u8> {
@@ -378,6 +985,21 @@ where R: RefCounter {
 		}
 	}
 
+	/// Returns an iterator over the bytes which owns the underlying bytes.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let bytes: Vec<u8> = SharedString::from("foo").into_bytes_iter().collect();
+	///
+	/// assert_eq!(bytes, b"foo");
+	/// ```
+	#[inline]
+	pub fn into_bytes_iter(self) -> IntoBytes<R> {
+		IntoBytes::new(self.start, self.len, self.bytes)
+	}
+
 	/// Returns the underlying Bytes from which this `SharedString` was created.
 	///
 	/// Tries to avoid a call to `clone` if the underlying data is not used
@@ -401,6 +1023,73 @@ where R: RefCounter {
 		unsafe { String::from_utf8_unchecked(vec) }
 	}
 
+	/// Returns a borrowed `Cow` of this `SharedString`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// # use std::borrow::Cow;
+	/// let foo = SharedString::from("foo");
+	///
+	/// assert_eq!(Cow::Borrowed("foo"), foo.as_cow());
+	/// ```
+	#[inline]
+	pub fn as_cow(&self) -> Cow<'_, str> {
+		Cow::Borrowed(self.as_str())
+	}
+
+	/// Converts this `SharedString` into an owned `Cow`.
+	///
+	/// Avoids a copy if the conditions in [into_string](#method.into_string)
+	/// are met.
+	#[inline]
+	pub fn into_cow(self) -> Cow<'static, str> {
+		Cow::Owned(self.into_string())
+	}
+
+	/// Copies this `SharedString` into an `Arc<str>`.
+	///
+	/// The data always gets copied, since an `Arc<str>` stores the reference
+	/// counter in the same allocation as the string.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// # use std::sync::Arc;
+	/// let foo = SharedString::from("foo bar");
+	///
+	/// let arc: Arc<str> = foo.idx(4..).into_arc_str();
+	/// assert_eq!(&*arc, "bar");
+	/// ```
+	#[inline]
+	pub fn into_arc_str(self) -> Arc<str> {
+		Arc::from(self.as_str())
+	}
+
+	/// Converts this `SharedString` into a `SharedStringBuilder` to append
+	/// more data.
+	///
+	/// Avoids a copy if the conditions in [into_string](#method.into_string)
+	/// are met.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("foo");
+	/// let mut builder = foo.into_builder();
+	/// builder.push_str("bar");
+	///
+	/// let foobar: SharedString = builder.freeze();
+	/// assert_eq!(foobar, "foobar");
+	/// ```
+	#[inline]
+	pub fn into_builder(self) -> SharedStringBuilder {
+		self.into()
+	}
+
 	/// Returns the underlying Bytes as a `String` from which this
 	/// `SharedString` was created.
 	///
@@ -412,46 +1101,80 @@ where R: RefCounter {
 		unsafe { String::from_utf8_unchecked(vec) }
 	}
 
-	/// Pushes a char to the `String` returned by
-	/// [into_string](#method.into_string).
+	/// Appends a char to the end of this `SharedString`.
 	///
-	/// If the conditions in `into_string` are met no `clone` is perfomed.
+	/// See [push_str](#method.push_str).
 	///
 	/// ## Example
 	///
 	/// ```
 	/// # use shared_string::SharedString;
-	/// let fooba = SharedString::from("fooba");
-	/// let foobar = fooba.push('r');
+	/// let mut foobar = SharedString::from("fooba");
+	/// foobar.push('r');
 	///
 	/// assert_eq!(foobar, "foobar");
 	/// ```
 	#[inline]
-	pub fn push(self, ch: char) -> String {
-		let mut s = self.into_string();
-		s.push(ch);
-		s
+	pub fn push(&mut self, ch: char) {
+		self.push_str(ch.encode_utf8(&mut [0; 4]))
 	}
 
-	/// Pushes a string slice to the `String` returned by
-	/// [into_string](#method.into_string).
+	/// Appends a string slice to the end of this `SharedString`.
+	///
+	/// If the underlying bytes are not shared with another `SharedString`
+	/// the buffer and the reference counter are reused, the buffer is grown
+	/// with a reallocation which the allocator might perform in place. Else
+	/// the bytes of this `SharedString` get copied first, so other instances
+	/// are never affected (copy-on-write).
 	///
-	/// If the conditions in `into_string` are met no `clone` is perfomed.
+	/// The underlying bytes never hold spare capacity, to build a string
+	/// piece by piece use a
+	/// [SharedStringBuilder](struct.SharedStringBuilder.html) instead, see
+	/// [into_builder](#method.into_builder).
+	///
+	/// Before version 0.2 this method consumed the `SharedString` and
+	/// returned a `String`, use [into_string](#method.into_string) and
+	/// `String::push_str` for that.
 	///
 	/// ## Example
 	///
 	/// ```
 	/// # use shared_string::SharedString;
-	/// let foo = SharedString::from("foo");
-	/// let foobar = foo.push_str("bar");
+	/// let mut foobar = SharedString::from("foo");
+	/// let foo = foobar.clone();
+	/// foobar.push_str("bar");
 	///
 	/// assert_eq!(foobar, "foobar");
+	/// assert_eq!(foo, "foo");
 	/// ```
-	#[inline]
-	pub fn push_str(self, string: &str) -> String {
-		let mut s = self.into_string();
-		s.push_str(string);
-		s
+	pub fn push_str(&mut self, string: &str) {
+		if string.is_empty() {
+			return
+		}
+
+		let (start, end) = (self.start, self.start + self.len);
+		if let Some(bytes) = self.bytes.get_mut() {
+			// not shared, reuse the buffer and the reference counter
+			let mut vec = mem::take(bytes).into_vec();
+			vec.truncate(end);
+			vec.drain(..start);
+			// exact so into_boxed_slice doesn't need to shrink
+			vec.reserve_exact(string.len());
+			vec.extend_from_slice(string.as_bytes());
+
+			self.start = 0;
+			self.len = vec.len();
+			*bytes = vec.into_boxed_slice();
+			return
+		}
+
+		let mut vec = Vec::with_capacity(self.len + string.len());
+		vec.extend_from_slice(self.as_bytes());
+		vec.extend_from_slice(string.as_bytes());
+
+		self.start = 0;
+		self.len = vec.len();
+		self.bytes = vec.into_boxed_slice().into();
 	}
 
 	/// Splits the `SharedString` into two at the given index.
@@ -462,26 +1185,141 @@ where R: RefCounter {
 	///
 	/// ## Panics
 	///
-	/// Panics if `at` is not at a char boundary.
+	/// Panics if `at` is out-of-bounds or not at a char boundary. See
+	/// [try_split_off](#method.try_split_off) for a non-panicking version.
 	#[inline]
 	pub fn split_off(&mut self, at: usize) -> Self {
+		match self.try_split_off(at) {
+			Ok(s) => s,
+			Err(e) => panic!("{}", e)
+		}
+	}
+
+	/// Splits the `SharedString` into two at the closest char boundary
+	/// less than or equal to `at`.
+	///
+	/// Unlike [split_off](#method.split_off) this never splits a char and
+	/// doesn't panic if `at` is inside of one.
+	///
+	/// ## Panics
+	///
+	/// Panics if `at` is out-of-bounds.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut foo = SharedString::from("foo好");
+	/// let bar = foo.split_off_floor(5);
+	///
+	/// assert_eq!(foo, "foo");
+	/// assert_eq!(bar, "好");
+	/// ```
+	pub fn split_off_floor(&mut self, at: usize) -> Self {
+		if at > self.len {
+			panic!("{}", SplitError::OutOfBounds)
+		}
+
+		let at = self.floor_char_boundary(at);
+		self.split_off(at)
+	}
+
+	/// Splits the `SharedString` into two at the given index.
+	///
+	/// This is the non-panicking alternative to
+	/// [split_off](#method.split_off).
+	///
+	/// ## Errors
+	///
+	/// Returns a `SplitError` if `at` is out-of-bounds or not at a char
+	/// boundary, `self` is left untouched.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::{SharedString, SplitError};
+	/// let mut foo = SharedString::from("foo好");
+	///
+	/// assert_eq!(foo.try_split_off(10), Err(SplitError::OutOfBounds));
+	/// assert_eq!(foo.try_split_off(4), Err(SplitError::NotCharBoundary));
+	///
+	/// let bar = foo.try_split_off(3).unwrap();
+	/// assert_eq!(foo, "foo");
+	/// assert_eq!(bar, "好");
+	/// ```
+	pub fn try_split_off(&mut self, at: usize) -> Result<Self, SplitError> {
 		if at == 0 {
 			let c = self.clone();
 			self.len = 0;
-			return c
+			return Ok(c)
+		}
+
+		if at > self.len {
+			return Err(SplitError::OutOfBounds)
 		}
 
-		// panics if at > self.len
-		assert!(self.is_char_boundary(at));
+		if !self.is_char_boundary(at) {
+			return Err(SplitError::NotCharBoundary)
+		}
 
 		let n_len = self.len - at;
 		self.len = at;
 
-		Self {
+		Ok(Self {
 			start: self.start + at,
 			len: n_len,
 			bytes: self.bytes.clone()
+		})
+	}
+
+	/// Divides the `SharedString` into two at the given index.
+	///
+	/// Behaves like [split_off](#method.split_off) but doesn't modify
+	/// `self`.
+	///
+	/// ## Panics
+	///
+	/// Panics if `at` is out-of-bounds or not at a char boundary. See
+	/// [split_at_checked](#method.split_at_checked) for a non-panicking
+	/// version.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	/// let (foo, bar) = foobar.split_at(3);
+	///
+	/// assert_eq!(foo, "foo");
+	/// assert_eq!(bar, "bar");
+	/// ```
+	#[inline]
+	pub fn split_at(&self, at: usize) -> (Self, Self) {
+		self.split_at_checked(at)
+			.expect("index out of bounds or not at a char boundary")
+	}
+
+	/// Divides the `SharedString` into two at the given index.
+	///
+	/// Returns `None` if `at` is out-of-bounds or not at a char boundary.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a好");
+	///
+	/// assert!(s.split_at_checked(2).is_none());
+	/// assert!(s.split_at_checked(5).is_none());
+	/// assert_eq!(s.split_at_checked(1).unwrap().1, "好");
+	/// ```
+	#[inline]
+	pub fn split_at_checked(&self, at: usize) -> Option<(Self, Self)> {
+		if at > self.len || !self.is_char_boundary(at) {
+			return None
 		}
+
+		Some((self.idx(..at), self.idx(at..)))
 	}
 
 	/// Returns an iterator which returns for every "segment" a `SharedString`.
@@ -490,6 +1328,10 @@ where R: RefCounter {
 	///
 	/// u8 will be replaced when [Pattern](https://doc.rust-lang.org/std/str/pattern/trait.Pattern.html) gets stabilized.
 	///
+	/// A trailing byte does not produce an empty segment, so this behaves
+	/// like [str::split_terminator](https://doc.rust-lang.org/std/primitive.str.html#method.split_terminator).
+	/// The iterator can also be consumed from the back.
+	///
 	/// ## Example
 	///
 	/// ```
@@ -506,386 +1348,4040 @@ where R: RefCounter {
 		Split::new(self.start, self.len, self.bytes, byte)
 	}
 
-	/// Returns an iterator which returns for every line a `SharedString`.
-	///
-	/// Be aware that this doens't behave exactly like [lines](#method.lines).
+	/// Returns an iterator which returns for every "segment" a `SharedString`
+	/// together with the byte index at which it starts.
 	///
-	/// This implementation returns an empty line at the if there is a `\n`
-	/// byte.
+	/// Segments are split the same way as in [split](#method.split). The
+	/// index is relative to this `SharedString`.
 	///
 	/// ## Example
 	///
 	/// ```
 	/// # use shared_string::SharedString;
-	/// let mut lines = SharedString::from("foo\r\nbar\n\nbaz\n").lines();
-	///
-	/// assert_eq!("foo", lines.next().unwrap());
-	/// assert_eq!("bar", lines.next().unwrap());
-	/// assert_eq!("", lines.next().unwrap());
-	/// assert_eq!("baz", lines.next().unwrap());
+	/// let mut split = SharedString::from("foo bar").split_indices(b' ');
 	///
-	/// assert_eq!(None, lines.next());
+	/// assert_eq!((0, "foo".into()), split.next().unwrap());
+	/// assert_eq!((4, "bar".into()), split.next().unwrap());
+	/// assert_eq!(None, split.next());
 	/// ```
 	#[inline]
-	pub fn lines(self) -> Lines<R> {
-		Lines::new(self.start, self.len, self.bytes)
+	pub fn split_indices(self, byte: u8) -> SplitIndices<R> {
+		SplitIndices::new(self.start, self.len, self.bytes, byte)
 	}
 
-	/// Shortens this `SharedString` to the specified length.
+	/// Returns an iterator which returns at most `n` segments.
 	///
-	/// If `new_len` is greater than the current length, nothing happens.
+	/// The last segment contains the remaining bytes including any further
+	/// `byte`s.
 	///
-	/// ## Panics
+	/// ## Example
 	///
-	/// Panics if `new_len` does not lie on a char boundary.
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from("a:b:c").splitn(2, b':');
+	///
+	/// assert_eq!("a", split.next().unwrap());
+	/// assert_eq!("b:c", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
 	#[inline]
-	pub fn truncate(&mut self, new_len: usize) {
-		if new_len < self.len {
-			assert!(self.is_char_boundary(new_len));
-			self.len = new_len;
-		}
+	pub fn splitn(self, n: usize, byte: u8) -> SplitN<R> {
+		SplitN::new(self.start, self.len, self.bytes, byte, n)
 	}
-}
 
-impl<R> fmt::Display for SharedGenString<R>
-where R: RefCounter {
+	/// Returns an iterator which returns for every "segment" a `SharedString`
+	/// starting from the end.
+	///
+	/// Returns the same segments as [split](#method.split) in reverse order.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from("a.b.c").rsplit(b'.');
+	///
+	/// assert_eq!("c", split.next().unwrap());
+	/// assert_eq!("b", split.next().unwrap());
+	/// assert_eq!("a", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
 	#[inline]
-	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		fmt::Display::fmt(self.as_str(), f)
+	pub fn rsplit(self, byte: u8) -> RSplit<R> {
+		RSplit::new(self.start, self.len, self.bytes, byte)
 	}
-}
 
-impl<R> fmt::Debug for SharedGenString<R>
-where R: RefCounter {
+	/// Returns an iterator which returns at most `n` segments starting from
+	/// the end.
+	///
+	/// The last segment contains the remaining bytes at the start including
+	/// any further `byte`s.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from("foo.tar.gz").rsplitn(2, b'.');
+	///
+	/// assert_eq!("gz", split.next().unwrap());
+	/// assert_eq!("foo.tar", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
 	#[inline]
-	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		fmt::Debug::fmt(self.as_str(), f)
+	pub fn rsplitn(self, n: usize, byte: u8) -> RSplitN<R> {
+		RSplitN::new(self.start, self.len, self.bytes, byte, n)
 	}
-}
 
-impl<R> hash::Hash for SharedGenString<R>
-where R: RefCounter {
+	/// Returns an iterator which returns for every "segment" a `SharedString`
+	/// including the `byte` at the end.
+	///
+	/// If the `SharedString` doesn't end with `byte` the last segment will
+	/// not contain it.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from("foo\nbar").split_inclusive(b'\n');
+	///
+	/// assert_eq!("foo\n", split.next().unwrap());
+	/// assert_eq!("bar", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
 	#[inline]
-	fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
-		self.as_str().hash(hasher)
+	pub fn split_inclusive(self, byte: u8) -> SplitInclusive<R> {
+		SplitInclusive::new(self.start, self.len, self.bytes, byte)
 	}
-}
-
-impl<R> ops::Deref for SharedGenString<R>
-where R: RefCounter {
-	type Target = str;
 
-	#[inline]
-	fn deref(&self) -> &str {
-		// Safe because we know that Self contains valid utf8
-		unsafe { str::from_utf8_unchecked(self.as_bytes()) }
-	}
-}
+	/// Returns an iterator which returns for every "segment" between the
+	/// `pattern` a `SharedString`.
+	///
+	/// Behaves like [split](#method.split) but accepts a string slice.
+	///
+	/// ## Panics
+	///
+	/// Panics if `pattern` is empty.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from("foo, bar").split_str(", ");
+	///
+	/// assert_eq!("foo", split.next().unwrap());
+	/// assert_eq!("bar", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
+	#[inline]
+	pub fn split_str(self, pattern: &str) -> SplitStr<'_, R> {
+		SplitStr::new(self.start, self.len, self.bytes, pattern)
+	}
 
-impl<R> AsRef<str> for SharedGenString<R>
-where R: RefCounter {
+	/// Returns an iterator which returns for every "segment" a `SharedString`,
+	/// splitting at every byte contained in `bytes`.
+	///
+	/// Behaves like [split](#method.split) but accepts a set of bytes.
+	/// If `bytes` is empty the whole string is returned as one segment.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from("a b\tc").split_any(&[b' ', b'\t']);
+	///
+	/// assert_eq!("a", split.next().unwrap());
+	/// assert_eq!("b", split.next().unwrap());
+	/// assert_eq!("c", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
 	#[inline]
-	fn as_ref(&self) -> &str {
-		self
+	pub fn split_any(self, bytes: &[u8]) -> SplitAny<'_, R> {
+		SplitAny::new(self.start, self.len, self.bytes, bytes)
 	}
-}
 
-impl<R> borrow::Borrow<str> for SharedGenString<R>
-where R: RefCounter {
+	/// Returns an iterator which returns for every "segment" between the
+	/// matches of `re` a `SharedString`.
+	///
+	/// Behaves like [split](#method.split), a trailing match does not
+	/// produce an empty segment.
+	///
+	/// Only available with the `regex` feature.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// use regex::Regex;
+	///
+	/// let re = Regex::new(r"\s*,\s*").unwrap();
+	/// let mut split = SharedString::from("a , b,c").split_regex(&re);
+	///
+	/// assert_eq!("a", split.next().unwrap());
+	/// assert_eq!("b", split.next().unwrap());
+	/// assert_eq!("c", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
+	#[cfg(feature = "regex")]
 	#[inline]
-	fn borrow(&self) -> &str {
-		self
+	pub fn split_regex(self, re: &Regex) -> RegexSplit<'_, R> {
+		RegexSplit::new(self.start, self.len, self.bytes, re)
+	}
+
+	/// Returns an iterator which returns for every whitespace separated
+	/// "segment" a `SharedString`.
+	///
+	/// Behaves the same way as
+	/// [str::split_whitespace](https://doc.rust-lang.org/std/primitive.str.html#method.split_whitespace),
+	/// no empty segments are returned.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut split = SharedString::from(" foo \t bar\n").split_whitespace();
+	///
+	/// assert_eq!("foo", split.next().unwrap());
+	/// assert_eq!("bar", split.next().unwrap());
+	/// assert_eq!(None, split.next());
+	/// ```
+	#[inline]
+	pub fn split_whitespace(self) -> SplitWhitespace<R> {
+		SplitWhitespace::new(self.start, self.len, self.bytes)
+	}
+
+	/// Returns an iterator which returns chunks of at most `size` bytes,
+	/// starting at the beginning of the string.
+	///
+	/// A chunk never splits a char. If a char would be split, the chunk
+	/// ends before it and the char is returned in the next chunk, which
+	/// means chunks of non-ASCII text might be shorter than `size`.
+	/// A single char longer than `size` is returned as its own chunk.
+	///
+	/// ## Panics
+	///
+	/// Panics if `size` is zero.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let chunks: Vec<_> = SharedString::from("abcde").chunks(2).collect();
+	/// assert_eq!(chunks, ["ab", "cd", "e"]);
+	///
+	/// let chunks: Vec<_> = SharedString::from("aäb").chunks(2).collect();
+	/// assert_eq!(chunks, ["a", "ä", "b"]);
+	/// ```
+	#[inline]
+	pub fn chunks(self, size: usize) -> Chunks<R> {
+		Chunks::new(self.start, self.len, self.bytes, size)
+	}
+
+	/// Returns an iterator which returns chunks of at most `size` bytes,
+	/// starting at the end of the string.
+	///
+	/// Behaves like [chunks](#method.chunks) but in reverse.
+	///
+	/// ## Panics
+	///
+	/// Panics if `size` is zero.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let chunks: Vec<_> = SharedString::from("abcde").rchunks(2).collect();
+	/// assert_eq!(chunks, ["de", "bc", "a"]);
+	/// ```
+	#[inline]
+	pub fn rchunks(self, size: usize) -> RChunks<R> {
+		RChunks::new(self.start, self.len, self.bytes, size)
+	}
+
+	/// Returns an iterator which returns every extended grapheme cluster
+	/// as a `SharedString`.
+	///
+	/// A grapheme cluster is what a user perceives as a single character,
+	/// for example a letter with combining marks or an emoji sequence.
+	///
+	/// Only available with the `unicode` feature.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a\u{308}b");
+	/// let graphemes: Vec<_> = s.graphemes().collect();
+	///
+	/// assert_eq!(graphemes, ["a\u{308}", "b"]);
+	/// ```
+	#[cfg(feature = "unicode")]
+	#[inline]
+	pub fn graphemes(self) -> Graphemes<R> {
+		Graphemes::new(self.start, self.len, self.bytes)
+	}
+
+	/// Splits the `SharedString` on the first occurrence of `byte`.
+	///
+	/// Returns the part before and the part after the byte, the byte itself
+	/// is not included. Both parts share the same underlying bytes.
+	///
+	/// Returns `None` if `byte` was not found.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let line = SharedString::from("foo: bar: baz");
+	/// let (key, value) = line.split_once(b':').unwrap();
+	///
+	/// assert_eq!(key, "foo");
+	/// assert_eq!(value, " bar: baz");
+	/// ```
+	#[inline]
+	pub fn split_once(&self, byte: u8) -> Option<(Self, Self)> {
+		let at = iter::find_byte(byte, self.as_bytes())?;
+		Some((self.idx(..at), self.idx((at + 1)..)))
+	}
+
+	/// Splits the `SharedString` on the last occurrence of `byte`.
+	///
+	/// Returns the part before and the part after the byte, the byte itself
+	/// is not included. Both parts share the same underlying bytes.
+	///
+	/// Returns `None` if `byte` was not found.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let line = SharedString::from("foo: bar: baz");
+	/// let (key, value) = line.rsplit_once(b':').unwrap();
+	///
+	/// assert_eq!(key, "foo: bar");
+	/// assert_eq!(value, " baz");
+	/// ```
+	#[inline]
+	pub fn rsplit_once(&self, byte: u8) -> Option<(Self, Self)> {
+		let at = iter::rfind_byte(byte, self.as_bytes())?;
+		Some((self.idx(..at), self.idx((at + 1)..)))
+	}
+
+	/// Splits the `SharedString` on the last occurrence of `pat`.
+	///
+	/// Returns the part before and the part after the pattern, the pattern
+	/// itself is not included. Both parts share the same underlying bytes.
+	///
+	/// Returns `None` if `pat` was not found.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let path = SharedString::from("std::io::Read");
+	/// let (module, name) = path.rsplit_once_str("::").unwrap();
+	///
+	/// assert_eq!(module, "std::io");
+	/// assert_eq!(name, "Read");
+	/// ```
+	#[inline]
+	pub fn rsplit_once_str(&self, pat: &str) -> Option<(Self, Self)> {
+		let at = self.as_str().rfind(pat)?;
+		Some((self.idx(..at), self.idx((at + pat.len())..)))
+	}
+
+	/// Splits the `SharedString` at the first whitespace into the first
+	/// token and the remainder.
+	///
+	/// Leading whitespace of the remainder is removed, leading whitespace of
+	/// the string itself is not, call [trim_start](#method.trim_start) first
+	/// if needed. Both parts share the same underlying bytes.
+	///
+	/// Returns `None` if the string contains no whitespace.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let line = SharedString::from("SET \t key value");
+	/// let (cmd, args) = line.split_at_first_whitespace().unwrap();
+	///
+	/// assert_eq!(cmd, "SET");
+	/// assert_eq!(args, "key value");
+	/// ```
+	#[inline]
+	pub fn split_at_first_whitespace(&self) -> Option<(Self, Self)> {
+		let at = self.as_str().find(char::is_whitespace)?;
+		Some((self.idx(..at), self.idx(at..).trim_start()))
+	}
+
+	/// Splits off the first char, returning it and the remaining
+	/// `SharedString`.
+	///
+	/// The remainder shares the same underlying bytes.
+	///
+	/// Returns `None` if the string is empty.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let (first, rest) = SharedString::from("äbc").split_first_char().unwrap();
+	///
+	/// assert_eq!(first, 'ä');
+	/// assert_eq!(rest, "bc");
+	/// assert_eq!(rest.as_full_str(), "äbc");
+	/// ```
+	#[inline]
+	pub fn split_first_char(self) -> Option<(char, Self)> {
+		let c = self.as_str().chars().next()?;
+		let c_len = c.len_utf8();
+		Some((c, Self::new_raw(
+			self.start + c_len,
+			self.len - c_len,
+			self.bytes
+		)))
+	}
+
+	/// Returns the first line as a `SharedString`.
+	///
+	/// The line ends before the first `\n` or `\r\n`, if there is none the
+	/// whole string is returned. Cheaper than creating a
+	/// [lines](#method.lines) iterator.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let response = SharedString::from("HTTP/1.1 200 OK\r\nHeader: v");
+	///
+	/// assert_eq!("HTTP/1.1 200 OK", response.first_line());
+	/// ```
+	pub fn first_line(&self) -> Self {
+		let mut end = match iter::find_byte(b'\n', self.as_bytes()) {
+			Some(at) => at,
+			None => return self.clone()
+		};
+
+		// a \r is only removed if it is followed by a \n
+		if end >= 1 && self.as_bytes()[end - 1] == b'\r' {
+			end -= 1;
+		}
+
+		self.idx(..end)
+	}
+
+	/// Returns everything after the first line as a `SharedString`.
+	///
+	/// The line ending is not included, if there is no line ending an empty
+	/// `SharedString` is returned. See [first_line](#method.first_line).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let response = SharedString::from("HTTP/1.1 200 OK\r\nHeader: v");
+	///
+	/// assert_eq!("Header: v", response.rest_after_first_line());
+	/// ```
+	pub fn rest_after_first_line(&self) -> Self {
+		match iter::find_byte(b'\n', self.as_bytes()) {
+			Some(at) => self.idx((at + 1)..),
+			None => self.idx(self.len..)
+		}
+	}
+
+	/// Returns an iterator which returns for every line a `SharedString`.
+	///
+	/// Behaves the same way as
+	/// [str::lines](https://doc.rust-lang.org/std/primitive.str.html#method.lines),
+	/// lines are split at `\n` or `\r\n` and a trailing line ending does
+	/// not produce an empty line.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut lines = SharedString::from("foo\r\nbar\n\nbaz\n").lines();
+	///
+	/// assert_eq!("foo", lines.next().unwrap());
+	/// assert_eq!("bar", lines.next().unwrap());
+	/// assert_eq!("", lines.next().unwrap());
+	/// assert_eq!("baz", lines.next().unwrap());
+	///
+	/// assert_eq!(None, lines.next());
+	/// ```
+	#[inline]
+	pub fn lines(self) -> Lines<R> {
+		Lines::new(self.start, self.len, self.bytes)
+	}
+
+	/// Returns an iterator which returns for every line a `SharedString`
+	/// including the line terminator (`\n` or `\r\n`).
+	///
+	/// Joining all lines returns the original `SharedString`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foo\r\nbar\n\nbaz");
+	/// let mut lines = foobar.lines_with_terminators();
+	///
+	/// assert_eq!("foo\r\n", lines.next().unwrap());
+	/// assert_eq!("bar\n", lines.next().unwrap());
+	/// assert_eq!("\n", lines.next().unwrap());
+	/// assert_eq!("baz", lines.next().unwrap());
+	/// assert_eq!(None, lines.next());
+	/// ```
+	#[inline]
+	pub fn lines_with_terminators(self) -> LinesWithTerminators<R> {
+		LinesWithTerminators::new(self.start, self.len, self.bytes)
+	}
+
+	/// Returns an iterator which returns for every line a `SharedString`
+	/// together with its byte range.
+	///
+	/// Lines are split the same way as in [lines](#method.lines). The range
+	/// is relative to this `SharedString` and excludes the line ending.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut lines = SharedString::from("foo\r\nbar").line_ranges();
+	///
+	/// assert_eq!((0..3, "foo".into()), lines.next().unwrap());
+	/// assert_eq!((5..8, "bar".into()), lines.next().unwrap());
+	/// assert_eq!(None, lines.next());
+	/// ```
+	#[inline]
+	pub fn line_ranges(self) -> LineRanges<R> {
+		LineRanges::new(self.start, self.len, self.bytes)
+	}
+
+	/// Returns an iterator which returns every `n` consecutive lines,
+	/// advancing one line at a time.
+	///
+	/// Lines are split the same way as in [lines](#method.lines) and all
+	/// share the same underlying bytes. If there are fewer than `n` lines
+	/// nothing is returned.
+	///
+	/// ## Panics
+	///
+	/// Panics if `n` is zero.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut windows = SharedString::from("a\nb\nc").line_windows(2);
+	///
+	/// assert_eq!(windows.next().unwrap(), ["a", "b"]);
+	/// assert_eq!(windows.next().unwrap(), ["b", "c"]);
+	/// assert_eq!(windows.next(), None);
+	/// ```
+	#[inline]
+	pub fn line_windows(self, n: usize) -> LineWindows<R> {
+		LineWindows::new(self.start, self.len, self.bytes, n)
+	}
+
+	/// Shortens this `SharedString` to the specified length.
+	///
+	/// If `new_len` is greater than the current length, nothing happens.
+	///
+	/// ## Panics
+	///
+	/// Panics if `new_len` does not lie on a char boundary.
+	#[inline]
+	pub fn truncate(&mut self, new_len: usize) {
+		if new_len < self.len {
+			assert!(self.is_char_boundary(new_len));
+			self.len = new_len;
+		}
+	}
+
+	/// Shortens this `SharedString` to the specified length.
+	///
+	/// This is the non-panicking alternative to [truncate](#method.truncate).
+	///
+	/// Returns `false` and leaves the `SharedString` unchanged if `new_len`
+	/// is greater than the current length or does not lie on a char
+	/// boundary.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut s = SharedString::from("a好");
+	///
+	/// assert!(!s.truncate_checked(2));
+	/// assert!(!s.truncate_checked(5));
+	/// assert!(s.truncate_checked(1));
+	/// assert_eq!("a", s);
+	/// ```
+	#[inline]
+	pub fn truncate_checked(&mut self, new_len: usize) -> bool {
+		if new_len > self.len || !self.is_char_boundary(new_len) {
+			return false
+		}
+
+		self.len = new_len;
+		true
+	}
+
+	/// Returns a `SharedString` with leading and trailing whitespace removed.
+	///
+	/// Whitespace is defined the same way as in
+	/// [str::trim](https://doc.rust-lang.org/std/primitive.str.html#method.trim).
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("\t foo \n");
+	///
+	/// assert_eq!("foo", foo.trim());
+	/// ```
+	#[inline]
+	pub fn trim(&self) -> Self {
+		self.trim_start().trim_end()
+	}
+
+	/// Returns a `SharedString` with leading whitespace removed.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("\t foo \n");
+	///
+	/// assert_eq!("foo \n", foo.trim_start());
+	/// ```
+	#[inline]
+	pub fn trim_start(&self) -> Self {
+		let trimmed = self.as_str().trim_start();
+		self.idx((self.len - trimmed.len())..)
+	}
+
+	/// Returns a `SharedString` with trailing whitespace removed.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("\t foo \n");
+	///
+	/// assert_eq!("\t foo", foo.trim_end());
+	/// ```
+	#[inline]
+	pub fn trim_end(&self) -> Self {
+		let trimmed = self.as_str().trim_end();
+		self.idx(..trimmed.len())
+	}
+
+	/// Returns a `SharedString` with leading and trailing ASCII whitespace
+	/// removed.
+	///
+	/// Whitespace is defined like in
+	/// [u8::is_ascii_whitespace](https://doc.rust-lang.org/std/primitive.u8.html#method.is_ascii_whitespace),
+	/// so a vertical tab is not removed.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("\t foo\r\n");
+	///
+	/// assert_eq!("foo", foo.trim_ascii());
+	/// ```
+	#[inline]
+	pub fn trim_ascii(&self) -> Self {
+		self.trim_ascii_start().trim_ascii_end()
+	}
+
+	/// Returns a `SharedString` with leading ASCII whitespace removed.
+	///
+	/// See [trim_ascii](#method.trim_ascii).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("\t foo\r\n");
+	///
+	/// assert_eq!("foo\r\n", foo.trim_ascii_start());
+	/// ```
+	#[inline]
+	pub fn trim_ascii_start(&self) -> Self {
+		let start = self.as_bytes()
+			.iter()
+			.position(|b| !b.is_ascii_whitespace())
+			.unwrap_or(self.len);
+		self.idx(start..)
+	}
+
+	/// Returns a `SharedString` with trailing ASCII whitespace removed.
+	///
+	/// See [trim_ascii](#method.trim_ascii).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foo = SharedString::from("\t foo\r\n");
+	///
+	/// assert_eq!("\t foo", foo.trim_ascii_end());
+	/// ```
+	#[inline]
+	pub fn trim_ascii_end(&self) -> Self {
+		let end = self.as_bytes()
+			.iter()
+			.rposition(|b| !b.is_ascii_whitespace())
+			.map(|i| i + 1)
+			.unwrap_or(0);
+		self.idx(..end)
+	}
+
+	/// Returns a `SharedString` with the prefix removed.
+	///
+	/// Returns `None` if the `SharedString` does not start with `prefix`.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let auth = SharedString::from("Bearer token");
+	///
+	/// assert_eq!("token", auth.strip_prefix("Bearer ").unwrap());
+	/// assert!(auth.strip_prefix("Basic ").is_none());
+	/// ```
+	#[inline]
+	pub fn strip_prefix(&self, prefix: &str) -> Option<Self> {
+		if self.starts_with(prefix) {
+			Some(self.idx(prefix.len()..))
+		} else {
+			None
+		}
+	}
+
+	/// Returns a `SharedString` with the suffix removed.
+	///
+	/// Returns `None` if the `SharedString` does not end with `suffix`.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let file = SharedString::from("foo.rs");
+	///
+	/// assert_eq!("foo", file.strip_suffix(".rs").unwrap());
+	/// assert!(file.strip_suffix(".txt").is_none());
+	/// ```
+	#[inline]
+	pub fn strip_suffix(&self, suffix: &str) -> Option<Self> {
+		if self.ends_with(suffix) {
+			Some(self.idx(..(self.len - suffix.len())))
+		} else {
+			None
+		}
+	}
+
+	/// Returns a `SharedString` with a leading UTF-8 byte order mark
+	/// (`U+FEFF`) removed.
+	///
+	/// If there is no byte order mark a clone is returned.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let file = SharedString::from("\u{feff}key = value");
+	///
+	/// assert_eq!("key = value", file.strip_bom());
+	/// assert_eq!("key = value", file.strip_bom().strip_bom());
+	/// ```
+	#[inline]
+	pub fn strip_bom(&self) -> Self {
+		self.strip_prefix("\u{feff}")
+			.unwrap_or_else(|| self.clone())
+	}
+
+	/// Returns a copy of this `SharedString` where each ASCII character is
+	/// mapped to its lowercase equivalent.
+	///
+	/// Non-ASCII characters are left untouched.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let header = SharedString::from("Content-Type");
+	///
+	/// assert_eq!("content-type", header.to_ascii_lowercase());
+	/// ```
+	#[inline]
+	pub fn to_ascii_lowercase(&self) -> Self {
+		self.as_str().to_ascii_lowercase().into()
+	}
+
+	/// Returns a copy of this `SharedString` where each ASCII character is
+	/// mapped to its uppercase equivalent.
+	///
+	/// Non-ASCII characters are left untouched.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let header = SharedString::from("Content-Type");
+	///
+	/// assert_eq!("CONTENT-TYPE", header.to_ascii_uppercase());
+	/// ```
+	#[inline]
+	pub fn to_ascii_uppercase(&self) -> Self {
+		self.as_str().to_ascii_uppercase().into()
+	}
+
+	/// Converts this string to its ASCII lower case equivalent in-place.
+	///
+	/// If the underlying bytes are shared with another `SharedString` they
+	/// get copied first, see [to_mut](#method.to_mut). Nothing is copied if
+	/// there is nothing to convert.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut header = SharedString::from("Content-Type");
+	/// let clone = header.clone();
+	/// header.make_ascii_lowercase();
+	///
+	/// assert_eq!("content-type", header);
+	/// assert_eq!("Content-Type", clone);
+	/// ```
+	#[inline]
+	pub fn make_ascii_lowercase(&mut self) {
+		if self.as_bytes().iter().any(u8::is_ascii_uppercase) {
+			self.to_mut().make_ascii_lowercase();
+		}
+	}
+
+	/// Converts this string to its ASCII upper case equivalent in-place.
+	///
+	/// If the underlying bytes are shared with another `SharedString` they
+	/// get copied first, see [to_mut](#method.to_mut). Nothing is copied if
+	/// there is nothing to convert.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let mut header = SharedString::from("Content-Type");
+	/// let clone = header.clone();
+	/// header.make_ascii_uppercase();
+	///
+	/// assert_eq!("CONTENT-TYPE", header);
+	/// assert_eq!("Content-Type", clone);
+	/// ```
+	#[inline]
+	pub fn make_ascii_uppercase(&mut self) {
+		if self.as_bytes().iter().any(u8::is_ascii_lowercase) {
+			self.to_mut().make_ascii_uppercase();
+		}
+	}
+
+	/// Checks that two strings are an ASCII case-insensitive match.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let header = SharedString::from("Content-Type");
+	///
+	/// assert!(header.eq_ignore_ascii_case("content-type"));
+	/// ```
+	#[inline]
+	pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+		self.as_str().eq_ignore_ascii_case(other)
+	}
+
+	/// Checks that two strings are a Unicode case-insensitive match.
+	///
+	/// Every char is converted to uppercase and then to lowercase, which
+	/// folds variants like `ς`, `σ` and `Σ` together.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let name = SharedString::from("Σίσυφος");
+	///
+	/// assert!(name.eq_ignore_case("ΣΊΣΥΦΟΣ"));
+	/// assert!(!name.eq_ignore_case("Sisyphus"));
+	/// ```
+	pub fn eq_ignore_case(&self, other: &str) -> bool {
+		fn fold(s: &str) -> impl Iterator<Item = char> + '_ {
+			s.chars()
+				.flat_map(char::to_uppercase)
+				.flat_map(char::to_lowercase)
+		}
+
+		fold(self.as_str()).eq(fold(other))
+	}
+
+	/// Returns the lowercase equivalent of this `SharedString` as a new
+	/// `SharedString`.
+	///
+	/// Behaves the same way as
+	/// [str::to_lowercase](https://doc.rust-lang.org/std/primitive.str.html#method.to_lowercase),
+	/// the length might change.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("ὈΔΥΣΣΕΎΣ");
+	///
+	/// assert_eq!("ὀδυσσεύς", s.to_lowercase());
+	/// ```
+	#[inline]
+	pub fn to_lowercase(&self) -> Self {
+		self.as_str().to_lowercase().into()
+	}
+
+	/// Returns the uppercase equivalent of this `SharedString` as a new
+	/// `SharedString`.
+	///
+	/// Behaves the same way as
+	/// [str::to_uppercase](https://doc.rust-lang.org/std/primitive.str.html#method.to_uppercase),
+	/// the length might change.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("tschüß");
+	///
+	/// assert_eq!("TSCHÜSS", s.to_uppercase());
+	/// ```
+	#[inline]
+	pub fn to_uppercase(&self) -> Self {
+		self.as_str().to_uppercase().into()
+	}
+
+	/// Parses this `SharedString` into another type.
+	///
+	/// Behaves the same way as
+	/// [str::parse](https://doc.rust-lang.org/std/primitive.str.html#method.parse).
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let line = SharedString::from("content-length: 42");
+	/// let (_, value) = line.split_once(b':').unwrap();
+	///
+	/// assert_eq!(42, value.trim().parse::<u32>().unwrap());
+	/// ```
+	#[inline]
+	pub fn parse<F>(&self) -> Result<F, F::Err>
+	where F: str::FromStr {
+		self.as_str().parse()
+	}
+
+	/// Returns `true` if the given pattern matches a substring of this
+	/// `SharedString`.
+	///
+	/// The pattern can be a `char`, a `&str` or another `SharedString`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	///
+	/// assert!(foobar.contains('b'));
+	/// assert!(foobar.contains("oba"));
+	/// assert!(foobar.contains(SharedString::from("bar")));
+	/// ```
+	#[inline]
+	pub fn contains<P>(&self, pat: P) -> bool
+	where P: Pattern {
+		pat.is_contained_in(self.as_str())
+	}
+
+	/// Returns `true` if the given pattern matches a prefix of this
+	/// `SharedString`.
+	///
+	/// The pattern can be a `char`, a `&str` or another `SharedString`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	///
+	/// assert!(foobar.starts_with('f'));
+	/// assert!(foobar.starts_with("foo"));
+	/// assert!(!foobar.starts_with(SharedString::from("bar")));
+	/// ```
+	#[inline]
+	pub fn starts_with<P>(&self, pat: P) -> bool
+	where P: Pattern {
+		pat.is_prefix_of(self.as_str())
+	}
+
+	/// Returns `true` if the given pattern matches a suffix of this
+	/// `SharedString`.
+	///
+	/// The pattern can be a `char`, a `&str` or another `SharedString`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	///
+	/// assert!(foobar.ends_with('r'));
+	/// assert!(foobar.ends_with("bar"));
+	/// assert!(!foobar.ends_with(SharedString::from("foo")));
+	/// ```
+	#[inline]
+	pub fn ends_with<P>(&self, pat: P) -> bool
+	where P: Pattern {
+		pat.is_suffix_of(self.as_str())
+	}
+
+	/// Returns the byte index of the first match of `pat`.
+	///
+	/// Returns `None` if `pat` was not found.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	///
+	/// assert_eq!(Some(3), foobar.find_index("bar"));
+	/// assert_eq!(None, foobar.find_index("baz"));
+	/// ```
+	#[inline]
+	pub fn find_index(&self, pat: &str) -> Option<usize> {
+		self.as_str().find(pat)
+	}
+
+	/// Returns the first match of `pat` as a `SharedString`.
+	///
+	/// Returns `None` if `pat` was not found.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let foobar = SharedString::from("foobar");
+	/// let bar = foobar.find_str("bar").unwrap();
+	///
+	/// assert_eq!("bar", bar);
+	/// assert_eq!("foobar", bar.as_full_str());
+	/// ```
+	#[inline]
+	pub fn find_str(&self, pat: &str) -> Option<Self> {
+		let at = self.find_index(pat)?;
+		Some(self.idx(at..(at + pat.len())))
+	}
+
+	/// Returns an iterator over every non-overlapping match of `pat` and the
+	/// byte index at which it starts.
+	///
+	/// Every match is returned as a `SharedString`, no allocation is
+	/// performed.
+	///
+	/// ## Panics
+	///
+	/// Panics if `pat` is empty.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("abcXXXabcYYYabc");
+	/// let matches: Vec<_> = s.match_indices("abc").collect();
+	///
+	/// assert_eq!(matches[0].0, 0);
+	/// assert_eq!(matches[1].0, 6);
+	/// assert_eq!(matches[2].0, 12);
+	/// assert_eq!(matches[2].1, "abc");
+	/// ```
+	#[inline]
+	pub fn match_indices(self, pat: &str) -> MatchIndices<'_, R> {
+		MatchIndices::new(self.start, self.len, self.bytes, pat)
+	}
+
+	/// Returns an iterator over every non-overlapping match of `pat`.
+	///
+	/// Like [match_indices](#method.match_indices) but only returns the
+	/// matched `SharedString`, no allocation is performed.
+	///
+	/// ## Panics
+	///
+	/// Panics if `pat` is empty.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a = b + c + d");
+	///
+	/// assert_eq!(s.matches(" + ").count(), 2);
+	/// ```
+	#[inline]
+	pub fn matches(self, pat: &str) -> Matches<'_, R> {
+		Matches::new(self.start, self.len, self.bytes, pat)
+	}
+
+	/// Returns how many times `byte` appears in the string.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a,b,c");
+	///
+	/// assert_eq!(2, s.count_byte(b','));
+	/// assert_eq!(0, s.count_byte(b';'));
+	/// ```
+	#[inline]
+	pub fn count_byte(&self, byte: u8) -> usize {
+		iter::count_byte(byte, self.as_bytes())
+	}
+
+	/// Returns the number of non-overlapping matches of `pat`.
+	///
+	/// An empty `pat` matches at every char boundary.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("aaa");
+	///
+	/// assert_eq!(1, s.count_matches("aa"));
+	/// assert_eq!(3, s.count_matches("a"));
+	/// ```
+	#[inline]
+	pub fn count_matches(&self, pat: &str) -> usize {
+		self.as_str().matches(pat).count()
+	}
+
+	/// Replaces all matches of `from` with `to`.
+	///
+	/// Behaves the same way as
+	/// [str::replace](https://doc.rust-lang.org/std/primitive.str.html#method.replace)
+	/// but if nothing is replaced no allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("this is old");
+	///
+	/// assert_eq!("this is new", s.replace("old", "new"));
+	/// assert_eq!("this is old", s.replace("new", "old"));
+	/// ```
+	#[inline]
+	pub fn replace(&self, from: &str, to: &str) -> Self {
+		self.replacen(from, to, usize::MAX)
+	}
+
+	/// Replaces the first `count` matches of `from` with `to`.
+	///
+	/// Behaves the same way as
+	/// [str::replacen](https://doc.rust-lang.org/std/primitive.str.html#method.replacen)
+	/// but if nothing is replaced no allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("foo foo foo");
+	///
+	/// assert_eq!("bar bar foo", s.replacen("foo", "bar", 2));
+	/// ```
+	pub fn replacen(&self, from: &str, to: &str, count: usize) -> Self {
+		if count == 0 || !self.as_str().contains(from) {
+			return self.clone()
+		}
+
+		self.as_str().replacen(from, to, count).into()
+	}
+
+	/// Returns a substring of `SharedString` where the range is interpreted
+	/// as char indices instead of byte indices.
+	///
+	/// Returns `None` if the range is out-of-bounds. Since every char needs
+	/// to be visited this is `O(n)`.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a好b");
+	///
+	/// assert_eq!("好", s.get_chars(1..2).unwrap());
+	/// assert_eq!("好b", s.get_chars(1..).unwrap());
+	/// assert!(s.get_chars(2..4).is_none());
+	/// ```
+	pub fn get_chars<I>(&self, range: I) -> Option<Self>
+	where I: ops::RangeBounds<usize> {
+		let start = match range.start_bound() {
+			Bound::Included(&i) => i,
+			Bound::Excluded(&i) => i.checked_add(1)?,
+			Bound::Unbounded => 0
+		};
+
+		let end = match range.end_bound() {
+			Bound::Included(&i) => Some(i.checked_add(1)?),
+			Bound::Excluded(&i) => Some(i),
+			Bound::Unbounded => None
+		};
+
+		// the byte index of every char and the end
+		let mut indices = self.char_indices()
+			.map(|(i, _)| i)
+			.chain(core::iter::once(self.len));
+
+		let b_start = indices.nth(start)?;
+		let b_end = match end {
+			Some(end) if end < start => return None,
+			Some(end) if end == start => b_start,
+			Some(end) => indices.nth(end - start - 1)?,
+			None => self.len
+		};
+
+		Some(self.idx(b_start..b_end))
+	}
+
+	/// Returns a substring of `SharedString` where the range is interpreted
+	/// as char indices instead of byte indices.
+	///
+	/// See [get_chars](#method.get_chars).
+	///
+	/// ## Panics
+	///
+	/// Panics if the range is out-of-bounds.
+	#[inline]
+	pub fn idx_chars<I>(&self, range: I) -> Self
+	where I: ops::RangeBounds<usize> {
+		self.get_chars(range).expect("invalid range")
+	}
+
+	/// Creates a new `SharedString` by repeating this one `n` times.
+	///
+	/// ## Panics
+	///
+	/// Panics if the capacity would overflow, like `str::repeat`.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("ab");
+	///
+	/// assert_eq!("ababab", s.repeat(3));
+	/// ```
+	#[inline]
+	pub fn repeat(&self, n: usize) -> Self {
+		if n == 0 {
+			return Self::new()
+		}
+
+		self.as_str().repeat(n).into()
+	}
+
+	/// Creates a new `SharedString` with the chars in reverse order.
+	///
+	/// The string is reversed by `char` and not by grapheme cluster, so
+	/// combining marks end up before the char they belong to.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("abä");
+	///
+	/// assert_eq!("äba", s.reverse());
+	/// ```
+	#[inline]
+	pub fn reverse(&self) -> Self {
+		let mut string = String::with_capacity(self.len);
+		string.extend(self.as_str().chars().rev());
+		string.into()
+	}
+
+	/// Concatenates all `parts` into a new `SharedString`.
+	///
+	/// If `parts` contains only one element no allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let parts = [SharedString::from("foo"), SharedString::from("bar")];
+	///
+	/// assert_eq!("foobar", SharedString::concat(&parts));
+	/// ```
+	#[inline]
+	pub fn concat(parts: &[Self]) -> Self {
+		Self::join(parts, "")
+	}
+
+	/// Joins all `parts` with the separator `sep` into a new `SharedString`.
+	///
+	/// If `parts` contains only one element no allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let parts = [SharedString::from("foo"), SharedString::from("bar")];
+	///
+	/// assert_eq!("foo, bar", SharedString::join(&parts, ", "));
+	/// ```
+	#[inline]
+	pub fn join(parts: &[Self], sep: &str) -> Self {
+		Self::collect_string(parts.iter().cloned(), sep)
+	}
+
+	/// Joins all parts returned by `iter` with the separator `sep` into a new
+	/// `SharedString`.
+	///
+	/// Behaves like [join](#method.join) but accepts an iterator, for
+	/// example one returned by [split](#method.split). Capacity is reserved
+	/// for the first two parts and the separators counted by the
+	/// `size_hint` of `iter`, the rest grows as needed.
+	///
+	/// If `iter` returns only one element no allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("a,b,c");
+	///
+	/// assert_eq!("a;b;c", SharedString::collect_string(s.split(b','), ";"));
+	/// ```
+	pub fn collect_string<I>(iter: I, sep: &str) -> Self
+	where I: IntoIterator<Item = Self> {
+		let mut iter = iter.into_iter();
+		let first = match iter.next() {
+			Some(first) => first,
+			None => return Self::new()
+		};
+		let second = match iter.next() {
+			Some(second) => second,
+			None => return first
+		};
+
+		Self::join_parts(&first, &second, iter, sep).into()
+	}
+
+	// joins first, second and the remaining parts with sep
+	fn join_parts<O, I>(
+		first: &str,
+		second: &str,
+		rest: I,
+		sep: &str
+	) -> String
+	where
+		O: RefCounter,
+		I: Iterator<Item = SharedGenString<O>> {
+		// only reserve what is known, the lengths of the remaining
+		// parts are not
+		let seps = rest.size_hint().0.saturating_add(1);
+		let cap = first.len()
+			.saturating_add(second.len())
+			.saturating_add(sep.len().saturating_mul(seps));
+		let mut s = String::new();
+		// a size_hint that is too large should not abort, push_str grows
+		// the string anyway
+		let _ = s.try_reserve(cap);
+		s.push_str(first);
+		s.push_str(sep);
+		s.push_str(second);
+		for part in rest {
+			s.push_str(sep);
+			s.push_str(&part);
+		}
+
+		s
+	}
+
+	/// Returns a `SharedString` with all prefixes and suffixes that match
+	/// the pattern repeatedly removed.
+	///
+	/// The pattern can be a `char`, a `&str` or another `SharedString`.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("\"quoted\"");
+	///
+	/// assert_eq!("quoted", s.trim_matches('"'));
+	/// ```
+	#[inline]
+	pub fn trim_matches<P>(&self, pat: P) -> Self
+	where P: Pattern {
+		let start_trimmed = pat.trim_start_of(self.as_str());
+		let start = self.len - start_trimmed.len();
+		let trimmed = pat.trim_end_of(start_trimmed);
+		self.idx(start..(start + trimmed.len()))
+	}
+
+	/// Returns a `SharedString` with all prefixes that match the pattern
+	/// repeatedly removed.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("../../file");
+	///
+	/// assert_eq!("file", s.trim_start_matches("../"));
+	/// ```
+	#[inline]
+	pub fn trim_start_matches<P>(&self, pat: P) -> Self
+	where P: Pattern {
+		let trimmed = pat.trim_start_of(self.as_str());
+		self.idx((self.len - trimmed.len())..)
+	}
+
+	/// Returns a `SharedString` with all suffixes that match the pattern
+	/// repeatedly removed.
+	///
+	/// No allocation is performed.
+	///
+	/// ## Example
+	///
+	/// ```
+	/// # use shared_string::SharedString;
+	/// let s = SharedString::from("1.500");
+	///
+	/// assert_eq!("1.5", s.trim_end_matches('0'));
+	/// ```
+	#[inline]
+	pub fn trim_end_matches<P>(&self, pat: P) -> Self
+	where P: Pattern {
+		let trimmed = pat.trim_end_of(self.as_str());
+		self.idx(..trimmed.len())
+	}
+}
+
+impl<R> fmt::Display for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.pad(self.as_str())
+	}
+}
+
+impl<R> fmt::Debug for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(self.as_str(), f)
+	}
+}
+
+impl<R> hash::Hash for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+		self.as_str().hash(hasher)
+	}
+}
+
+impl<R> ops::Deref for SharedGenString<R>
+where R: RefCounter {
+	type Target = str;
+
+	#[inline]
+	fn deref(&self) -> &str {
+		// Safe because we know that Self contains valid utf8
+		unsafe { str::from_utf8_unchecked(self.as_bytes()) }
+	}
+}
+
+impl<R> AsRef<str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn as_ref(&self) -> &str {
+		self
+	}
+}
+
+impl<R> AsRef<[u8]> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn as_ref(&self) -> &[u8] {
+		self.as_bytes()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<R> AsRef<Path> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn as_ref(&self) -> &Path {
+		Path::new(self.as_str())
+	}
+}
+
+// Borrow<[u8]> is not implemented on purpose, str and [u8] hash differently
+// so looking up a SharedString key by bytes in a HashMap would never succeed
+// and Hash can only be consistent with one of them
+impl<R> borrow::Borrow<str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn borrow(&self) -> &str {
+		self
+	}
+}
+
+// need a custom eq
+impl<R, O> cmp::PartialEq<SharedGenString<O>> for SharedGenString<R>
+where
+	R: RefCounter,
+	O: RefCounter {
+	#[inline]
+	fn eq(&self, other: &SharedGenString<O>) -> bool {
+		// the same region of the same bytes is always equal
+		ptr::eq(self.as_bytes(), other.as_bytes()) ||
+			self.as_str() == other.as_str()
+	}
+}
+
+impl<R: RefCounter> cmp::Eq for SharedGenString<R> {}
+
+impl<R> cmp::PartialEq<str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl<R> cmp::PartialEq<&str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+impl cmp::PartialEq<SharedString> for str {
+	#[inline]
+	fn eq(&self, other: &SharedString) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl cmp::PartialEq<SharedString> for &str {
+	#[inline]
+	fn eq(&self, other: &SharedString) -> bool {
+		*self == other.as_str()
+	}
+}
+
+impl cmp::PartialEq<SharedSyncString> for str {
+	#[inline]
+	fn eq(&self, other: &SharedSyncString) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl cmp::PartialEq<SharedSyncString> for &str {
+	#[inline]
+	fn eq(&self, other: &SharedSyncString) -> bool {
+		*self == other.as_str()
+	}
+}
+
+impl<R> cmp::PartialEq<String> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn eq(&self, other: &String) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl cmp::PartialEq<SharedString> for String {
+	#[inline]
+	fn eq(&self, other: &SharedString) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl cmp::PartialEq<SharedSyncString> for String {
+	#[inline]
+	fn eq(&self, other: &SharedSyncString) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl<R> cmp::PartialEq<Cow<'_, str>> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn eq(&self, other: &Cow<'_, str>) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl cmp::PartialEq<SharedString> for Cow<'_, str> {
+	#[inline]
+	fn eq(&self, other: &SharedString) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl cmp::PartialEq<SharedSyncString> for Cow<'_, str> {
+	#[inline]
+	fn eq(&self, other: &SharedSyncString) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl<R> cmp::PartialEq<[u8]> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn eq(&self, other: &[u8]) -> bool {
+		self.as_bytes() == other
+	}
+}
+
+impl<R> cmp::PartialEq<&[u8]> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn eq(&self, other: &&[u8]) -> bool {
+		self.as_bytes() == *other
+	}
+}
+
+impl cmp::PartialEq<SharedString> for [u8] {
+	#[inline]
+	fn eq(&self, other: &SharedString) -> bool {
+		self == other.as_bytes()
+	}
+}
+
+impl cmp::PartialEq<SharedString> for &[u8] {
+	#[inline]
+	fn eq(&self, other: &SharedString) -> bool {
+		*self == other.as_bytes()
+	}
+}
+
+impl cmp::PartialEq<SharedSyncString> for [u8] {
+	#[inline]
+	fn eq(&self, other: &SharedSyncString) -> bool {
+		self == other.as_bytes()
+	}
+}
+
+impl cmp::PartialEq<SharedSyncString> for &[u8] {
+	#[inline]
+	fn eq(&self, other: &SharedSyncString) -> bool {
+		*self == other.as_bytes()
+	}
+}
+
+impl<R, O> cmp::PartialOrd<SharedGenString<O>> for SharedGenString<R>
+where
+	R: RefCounter,
+	O: RefCounter {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedGenString<O>) -> Option<cmp::Ordering> {
+		Some(self.as_str().cmp(other.as_str()))
+	}
+}
+
+impl<R> cmp::Ord for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.as_str().cmp(other.as_str())
+	}
+}
+
+impl<R> cmp::PartialOrd<str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn partial_cmp(&self, other: &str) -> Option<cmp::Ordering> {
+		self.as_str().partial_cmp(other)
+	}
+}
+
+impl cmp::PartialOrd<SharedString> for str {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedString) -> Option<cmp::Ordering> {
+		self.partial_cmp(other.as_str())
+	}
+}
+
+impl cmp::PartialOrd<SharedSyncString> for str {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedSyncString) -> Option<cmp::Ordering> {
+		self.partial_cmp(other.as_str())
+	}
+}
+
+impl<R> cmp::PartialOrd<&str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn partial_cmp(&self, other: &&str) -> Option<cmp::Ordering> {
+		self.as_str().partial_cmp(*other)
+	}
+}
+
+impl cmp::PartialOrd<SharedString> for &str {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedString) -> Option<cmp::Ordering> {
+		(*self).partial_cmp(other.as_str())
+	}
+}
+
+impl cmp::PartialOrd<SharedSyncString> for &str {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedSyncString) -> Option<cmp::Ordering> {
+		(*self).partial_cmp(other.as_str())
+	}
+}
+
+impl<R> cmp::PartialOrd<String> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn partial_cmp(&self, other: &String) -> Option<cmp::Ordering> {
+		self.as_str().partial_cmp(other.as_str())
+	}
+}
+
+impl cmp::PartialOrd<SharedString> for String {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedString) -> Option<cmp::Ordering> {
+		self.as_str().partial_cmp(other.as_str())
+	}
+}
+
+impl cmp::PartialOrd<SharedSyncString> for String {
+	#[inline]
+	fn partial_cmp(&self, other: &SharedSyncString) -> Option<cmp::Ordering> {
+		self.as_str().partial_cmp(other.as_str())
+	}
+}
+
+impl<R> Default for SharedGenString<R>
+where R: RefCounter {
+	/// Creates a new empty `SharedString`.
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<R, O> FromIterator<SharedGenString<O>> for SharedGenString<R>
+where
+	R: RefCounter,
+	O: RefCounter {
+	/// Concatenates all items.
+	///
+	/// Reserves capacity like
+	/// [collect_string](struct.SharedGenString.html#method.collect_string).
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = SharedGenString<O>> {
+		let mut iter = iter.into_iter();
+		let first = match iter.next() {
+			Some(first) => first,
+			None => return Self::new()
+		};
+		let second = match iter.next() {
+			Some(second) => second,
+			None => return first.as_str().into()
+		};
+
+		Self::join_parts(&first, &second, iter, "").into()
+	}
+}
+
+impl<R> FromIterator<char> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = char> {
+		// String already reserves with the size_hint
+		iter.into_iter().collect::<String>().into()
+	}
+}
+
+impl<R> Extend<char> for SharedGenString<R>
+where R: RefCounter {
+	/// Appends all chars, copying the string once like
+	/// [push_str](struct.SharedGenString.html#method.push_str).
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = char> {
+		let mut iter = iter.into_iter();
+		let first = match iter.next() {
+			Some(first) => first,
+			None => return
+		};
+
+		let mut s = String::with_capacity(
+			self.len + first.len_utf8() + iter.size_hint().0
+		);
+		s.push_str(self);
+		s.push(first);
+		s.extend(iter);
+		*self = s.into();
+	}
+}
+
+impl<'a, R> Extend<&'a str> for SharedGenString<R>
+where R: RefCounter {
+	/// Appends all string slices, copying the string once like
+	/// [push_str](struct.SharedGenString.html#method.push_str).
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = &'a str> {
+		let mut iter = iter.into_iter();
+		let first = match iter.next() {
+			Some(first) => first,
+			None => return
+		};
+
+		let mut s = String::with_capacity(self.len + first.len());
+		s.push_str(self);
+		s.push_str(first);
+		s.extend(iter);
+		*self = s.into();
+	}
+}
+
+impl<R> IntoIterator for SharedGenString<R>
+where R: RefCounter {
+	type Item = char;
+	type IntoIter = IntoChars<R>;
+
+	/// Returns an iterator over the chars which owns the underlying bytes.
+	#[inline]
+	fn into_iter(self) -> IntoChars<R> {
+		IntoChars::new(self.start, self.len, self.bytes)
+	}
+}
+
+impl<R> From<String> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from(s: String) -> Self {
+		Self {
+			start: 0,
+			len: s.len(),
+			bytes: s.into_bytes().into_boxed_slice().into()
+		}
+	}
+}
+
+impl<R> From<&str> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from(s: &str) -> Self {
+		s.to_string().into()
+	}
+}
+
+impl<R> From<char> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from(c: char) -> Self {
+		Self::from(&*c.encode_utf8(&mut [0; 4]))
+	}
+}
+
+impl<R> From<Box<str>> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from(s: Box<str>) -> Self {
+		let bytes = s.into_boxed_bytes();
+		Self {
+			start: 0,
+			len: bytes.len(),
+			bytes: bytes.into()
+		}
+	}
+}
+
+impl<R> From<Arc<str>> for SharedGenString<R>
+where R: RefCounter {
+	/// Copies the string, since the reference counter of an `Arc<str>` can't
+	/// be reused.
+	#[inline]
+	fn from(s: Arc<str>) -> Self {
+		Self::from(&*s)
+	}
+}
+
+impl<R> From<Cow<'_, str>> for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn from(s: Cow<'_, str>) -> Self {
+		match s {
+			Cow::Borrowed(s) => s.into(),
+			Cow::Owned(s) => s.into()
+		}
+	}
+}
+
+impl<R> From<SharedGenString<R>> for Cow<'static, str>
+where R: RefCounter {
+	#[inline]
+	fn from(s: SharedGenString<R>) -> Self {
+		s.into_cow()
+	}
+}
+
+impl<R> TryFrom<Vec<u8>> for SharedGenString<R>
+where R: RefCounter {
+	type Error = FromUtf8Error;
+
+	/// See [from_utf8](struct.SharedGenString.html#method.from_utf8).
+	#[inline]
+	fn try_from(vec: Vec<u8>) -> Result<Self, Self::Error> {
+		Self::from_utf8(vec)
+	}
+}
+
+impl<R> TryFrom<Box<[u8]>> for SharedGenString<R>
+where R: RefCounter {
+	type Error = Utf8Error;
+
+	/// Validates that the bytes are valid UTF-8 without copying them.
+	#[inline]
+	fn try_from(bytes: Box<[u8]>) -> Result<Self, Self::Error> {
+		str::from_utf8(&bytes)?;
+		Ok(Self {
+			start: 0,
+			len: bytes.len(),
+			bytes: bytes.into()
+		})
+	}
+}
+
+impl<R> From<&SharedGenString<R>> for SharedGenString<R>
+where R: RefCounter {
+	/// Only increases the reference counter.
+	#[inline]
+	fn from(s: &SharedGenString<R>) -> Self {
+		s.clone()
+	}
+}
+
+impl<R> str::FromStr for SharedGenString<R>
+where R: RefCounter {
+	type Err = Infallible;
+
+	#[inline]
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(s.into())
+	}
+}
+
+impl<R> From<SharedGenString<R>> for String
+where R: RefCounter {
+	#[inline]
+	fn from(s: SharedGenString<R>) -> Self {
+		s.into_string()
+	}
+}
+
+impl<R> From<SharedGenString<R>> for Arc<str>
+where R: RefCounter {
+	/// See [into_arc_str](struct.SharedGenString.html#method.into_arc_str).
+	#[inline]
+	fn from(s: SharedGenString<R>) -> Self {
+		s.into_arc_str()
+	}
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+
+	use super::{SharedString, SharedSyncString};
+
+	#[test]
+	fn rc() {
+		let mut hello: SharedString = "Hello, World!".into();
+		assert_eq!(hello.len(), 13);
+
+		let world = hello.split_off(7);
+		assert_eq!(hello, "Hello, ");
+		assert_eq!(hello.len(), 7);
+		assert_eq!(world, "World!");
+		assert_eq!(world.len(), 6);
+	}
+
+	#[test]
+	fn arc() {
+		let mut hello: SharedSyncString = "Hello, World!".into();
+
+		let world = hello.split_off(7);
+
+		let handle = std::thread::spawn(move || {
+			assert_eq!(world, "World!");
+			assert_eq!(world.as_full_str(), "Hello, World!");
+		});
+
+		assert_eq!(hello, "Hello, ");
+		handle.join().unwrap();
+	}
+
+	#[test]
+	fn into() {
+		let hello = SharedString::from("Hello, World!");
+		let s = hello.into_string();
+		assert_eq!(s, "Hello, World!");
+
+		let mut hello: SharedString = s.into();
+		let world = hello.split_off(7);
+
+		let s = world.into_string();
+		assert_eq!(s, "World!");
+
+		assert!(hello != s.as_str());
+
+		let n_hello = SharedString::from("Hello, ");
+		assert_eq!(hello, n_hello);
+	}
+
+	#[test]
+	fn split_off_zero() {
+		let mut foobar = SharedString::from("foobar");
+		let n_foobar = foobar.split_off(0);
+		assert_eq!("", foobar);
+		assert_eq!("foobar", n_foobar);
+	}
+
+	#[test]
+	#[should_panic]
+	fn panic_char_boundary() {
+		let mut s = SharedString::from("abc 好 def");
+		let _ = s.split_off(5);
+	}
+
+	#[test]
+	#[should_panic]
+	fn panic_length() {
+		let mut s = SharedString::from("abc");
+		let _ = s.split_off(5);
+	}
+
+	#[test]
+	fn range_as_str() {
+		let raw = SharedString::from("Hello, World!");
+		let hello = &raw[..5];
+		let world = &raw[7..];
+
+		assert_eq!(hello, "Hello");
+		assert_eq!(world, "World!");
+	}
+
+	#[test]
+	fn range_with_get() {
+		let raw = SharedString::from("Hello, World!");
+		let hello = raw.get(..5).unwrap();
+		let world = raw.get(7..).unwrap();
+
+		assert_eq!(hello, "Hello");
+		assert_eq!(world, "World!");
+	}
+
+	#[test]
+	fn range_with_idx() {
+		let raw = SharedString::from("Hello, World!");
+		let hello = raw.idx(..5);
+		let world = raw.idx(7..);
+
+		assert_eq!(hello, "Hello");
+		assert_eq!(world, "World!");
+	}
+
+	#[test]
+	fn empty() {
+		let s = SharedString::from("");
+		assert_eq!(s.len(), 0);
+		assert!(s.is_empty());
+
+		assert!(s.get(..).is_some());
+		assert!(s.idx(..).is_empty());
+		assert!(s.get(1..).is_none());
+	}
+
+	#[test]
+	fn equal() {
+		let rc: SharedString = "Hello, World!".into();
+		let arc: SharedSyncString = "Hello, World!".into();
+		assert_eq!(rc, arc);
+	}
+
+	#[test]
+	fn split() {
+		let fullname = SharedString::from("Albert Einstein");
+		let mut split = fullname.split(b' ');
+		assert_eq!(split.next().unwrap(), "Albert");
+		assert_eq!(split.next().unwrap(), "Einstein");
+		assert_eq!(split.next(), None);
+	}
+
+	#[test]
+	fn lines() {
+		let quote = SharedString::from("Wenn die Menschen nur über das sprächen,\nwas sie begreifen,\r\ndann würde es sehr still auf der Welt sein.\n\r\n");
+		let mut lines = quote.lines();
+		assert_eq!(
+			lines.next().unwrap(),
+			"Wenn die Menschen nur über das sprächen,"
+		);
+		assert_eq!(lines.next().unwrap(), "was sie begreifen,");
+		assert_eq!(
+			lines.next().unwrap(),
+			"dann würde es sehr still auf der Welt sein."
+		);
+		assert_eq!(lines.next().unwrap(), "");
+		assert_eq!(lines.next(), None);
+
+		let empty = SharedString::from(" ");
+		let mut lines = empty.lines();
+		assert_eq!(" ", lines.next().unwrap());
+		assert_eq!(lines.next(), None);
+	}
+
+	#[test]
+	fn range_eq_str_range() {
+		let line = "foo: bar";
+		let at = line.find(':').unwrap();
+		let key = &line[..at];
+		let value = &line[(at + 2)..];
+
+		assert_eq!(key, "foo");
+		assert_eq!(value, "bar");
+
+		let line = SharedString::from(line);
+		let key = line.idx(..at);
+		let value = line.idx((at + 2)..);
+
+		assert_eq!(key, "foo");
+		assert_eq!(value, "bar");
+	}
+
+	#[test]
+	fn range_in_range() {
+		let line = "date: Mon, 30 Nov 2020 22:16:22 GMT\nserver: mw1271.eqiad.wmnet\nx-content-type-options: nosniff";
+		let mut lines = SharedString::from(line).lines();
+
+		let _ = lines.next().unwrap();
+		let line = lines.next().unwrap();
+
+		let at = line.find(':').unwrap();
+		assert_eq!(at, 6);
+
+		let key = line.idx(..at);
+		assert_eq!(key, "server");
+
+		let value = line.idx((at + 2)..);
+		assert_eq!(value, "mw1271.eqiad.wmnet");
+	}
+
+	#[test]
+	fn truncate() {
+		let mut foobar = SharedString::from("foobar");
+		foobar.truncate(3);
+		assert_eq!(foobar, "foo");
+	}
+
+	#[test]
+	fn hash_map_get_by_str() {
+		use std::collections::HashMap;
+
+		let mut map = HashMap::new();
+		let mut line = SharedString::from("content-type: text/html");
+		let value = line.split_off(12).idx(2..);
+		map.insert(line, value);
+
+		assert_eq!(map.get("content-type").unwrap(), "text/html");
+		assert!(!map.contains_key("content-length"));
+	}
+
+	#[test]
+	fn hash_map_get_by_bytes() {
+		use std::collections::HashMap;
+		use std::collections::hash_map::DefaultHasher;
+		use std::hash::{Hash, Hasher};
+
+		fn hash<T: Hash + ?Sized>(v: &T) -> u64 {
+			let mut hasher = DefaultHasher::new();
+			v.hash(&mut hasher);
+			hasher.finish()
+		}
+
+		// the reason there is no Borrow<[u8]>
+		let key = SharedString::from("foo");
+		assert_eq!(hash(&key), hash("foo"));
+		assert_ne!(hash(&key), hash(&b"foo"[..]));
+
+		// lookups by bytes need to go through str
+		let mut map = HashMap::new();
+		map.insert(key, 1);
+		let bytes: &[u8] = b"foo";
+		let key = str::from_utf8(bytes).unwrap();
+		assert_eq!(map.get(key), Some(&1));
+	}
+
+	#[test]
+	fn display_formatter_flags() {
+		let foo = SharedString::from("foo");
+		assert_eq!(format!("{:>6}", foo), "   foo");
+		assert_eq!(format!("{:<6}", foo), "foo   ");
+		assert_eq!(format!("{:^7}", foo), "  foo  ");
+		assert_eq!(format!("{:-^7}", foo), "--foo--");
+		assert_eq!(format!("{:.2}", foo), "fo");
+		assert_eq!(format!("{:>4.2}", foo), "  fo");
+	}
+
+	#[test]
+	fn split_once() {
+		let line = SharedString::from("key: value");
+		let (key, value) = line.split_once(b':').unwrap();
+		assert_eq!(key, "key");
+		assert_eq!(value, " value");
+		assert_eq!(value.as_full_str(), "key: value");
+
+		assert!(line.split_once(b'=').is_none());
+		assert!(line.rsplit_once(b'=').is_none());
+
+		let leading = SharedString::from(":value");
+		let (key, value) = leading.split_once(b':').unwrap();
+		assert_eq!(key, "");
+		assert_eq!(value, "value");
+
+		let trailing = SharedString::from("key:");
+		let (key, value) = trailing.rsplit_once(b':').unwrap();
+		assert_eq!(key, "key");
+		assert_eq!(value, "");
+	}
+
+	#[test]
+	fn rsplit_once() {
+		let path = SharedString::from("a.b.c");
+		let (head, ext) = path.rsplit_once(b'.').unwrap();
+		assert_eq!(head, "a.b");
+		assert_eq!(ext, "c");
+
+		let (first, rest) = path.split_once(b'.').unwrap();
+		assert_eq!(first, "a");
+		assert_eq!(rest, "b.c");
+	}
+
+	#[test]
+	fn split_rev() {
+		let inputs = [
+			"", " ", "  ", "a", "a b c", " a b ", "a  b", "foo bar ", " foo"
+		];
+
+		for input in &inputs {
+			let s = SharedString::from(*input);
+			let mut forward: Vec<_> = s.clone().split(b' ').collect();
+			forward.reverse();
+			let backward: Vec<_> = s.split(b' ').rev().collect();
+			assert_eq!(forward, backward, "input {:?}", input);
+		}
+	}
+
+	#[test]
+	fn split_both_ends() {
+		let s = SharedString::from("a b c d");
+		let mut split = s.split(b' ');
+		assert_eq!(split.next().unwrap(), "a");
+		assert_eq!(split.next_back().unwrap(), "d");
+		assert_eq!(split.next_back().unwrap(), "c");
+		assert_eq!(split.next().unwrap(), "b");
+		assert_eq!(split.next(), None);
+		assert_eq!(split.next_back(), None);
+
+		let s = SharedString::from("a  ");
+		let mut split = s.split(b' ');
+		assert_eq!(split.next_back().unwrap(), "");
+		assert_eq!(split.next().unwrap(), "a");
+		assert_eq!(split.next(), None);
+	}
+
+	#[test]
+	fn splitn() {
+		let s = SharedString::from("a:b:c");
+
+		assert_eq!(s.clone().splitn(0, b':').next(), None);
+
+		let all: Vec<_> = s.clone().splitn(1, b':').collect();
+		assert_eq!(all, ["a:b:c"]);
+
+		let all: Vec<_> = s.clone().splitn(2, b':').collect();
+		assert_eq!(all, ["a", "b:c"]);
+
+		let all: Vec<_> = s.clone().splitn(10, b':').collect();
+		assert_eq!(all, ["a", "b", "c"]);
+
+		let all: Vec<_> = SharedString::new().splitn(1, b':').collect();
+		assert!(all.is_empty());
+	}
+
+	#[test]
+	fn split_str() {
+		let s = SharedString::from("foo\r\nbar\r\n\r\nbaz");
+		let all: Vec<_> = s.split_str("\r\n").collect();
+		assert_eq!(all, ["foo", "bar", "", "baz"]);
+
+		let s = SharedString::from("a→b→→c→");
+		let all: Vec<_> = s.split_str("→").collect();
+		assert_eq!(all, ["a", "b", "", "c"]);
+
+		let s = SharedString::from("好好a好好");
+		let all: Vec<_> = s.split_str("好好").collect();
+		assert_eq!(all, ["", "a"]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn split_str_empty_pattern() {
+		let _ = SharedString::from("abc").split_str("");
+	}
+
+	#[test]
+	fn split_any() {
+		let s = SharedString::from("a b\tc");
+		let split: Vec<_> = s.split_any(b" \t").collect();
+		assert_eq!(split, ["a", "b", "c"]);
+
+		let s = SharedString::from("a,b;c d.e");
+		let split: Vec<_> = s.split_any(b",; .").collect();
+		assert_eq!(split, ["a", "b", "c", "d", "e"]);
+
+		let s = SharedString::from("\ta  b\t");
+		let split: Vec<_> = s.split_any(b" \t").collect();
+		assert_eq!(split, ["", "a", "", "b"]);
+
+		let s = SharedString::from("a b");
+		let split: Vec<_> = s.split_any(&[]).collect();
+		assert_eq!(split, ["a b"]);
+
+		assert_eq!(SharedString::new().split_any(b" ").next(), None);
+	}
+
+	#[test]
+	fn count_byte_and_matches() {
+		let s = SharedString::from("a\nb\nc\n");
+		assert_eq!(s.count_byte(b'\n'), 3);
+		assert_eq!(s.count_byte(b'a'), 1);
+		assert_eq!(s.count_byte(b'x'), 0);
+		assert_eq!(s.count_byte(b'\n'), s.lines().count());
+
+		let s = SharedString::from("xaaaax");
+		assert_eq!(s.idx(1..4).count_byte(b'x'), 0);
+		assert_eq!(s.count_matches("aa"), 2);
+		assert_eq!(s.idx(1..4).count_matches("aa"), 1);
+		assert_eq!(s.count_matches("aaa"), 1);
+		assert_eq!(s.count_matches("b"), 0);
+		assert_eq!(s.count_matches(""), 7);
+
+		let s = SharedString::from("äöä");
+		assert_eq!(s.count_matches("ä"), 2);
+	}
+
+	#[test]
+	fn eq_bytes() {
+		let s = SharedString::from("GET /index");
+		let method = s.clone().split(b' ').next().unwrap();
+		let get: &[u8] = b"GET";
+		assert_eq!(method, *get);
+		assert_eq!(method, get);
+		assert_ne!(method, &b"POST"[..]);
+		assert_eq!(*get, method);
+		assert_eq!(get, method);
+
+		let s = SharedSyncString::from("ä");
+		let bytes: &[u8] = &[0xc3, 0xa4];
+		assert_eq!(s, bytes);
+		assert_eq!(bytes, s);
+	}
+
+	#[test]
+	fn offset_in() {
+		let input = SharedString::from("GET /index HTTP/1.1\r\nHost: a\r\n");
+		let mut first = input.clone().lines().next().unwrap();
+		let mut path = first.split_off(4);
+		let version = path.split_off(7);
+
+		assert_eq!(first.offset_in(&input), Some(0));
+		assert_eq!(path.offset_in(&input), Some(4));
+		assert_eq!(version.offset_in(&input), Some(11));
+		assert_eq!(version.idx(5..).offset_in(&input), Some(16));
+		assert_eq!(version.idx(5..).offset_in(&version), Some(5));
+
+		// not contained
+		assert_eq!(path.offset_in(&version), None);
+		assert_eq!(input.offset_in(&path), None);
+
+		// different allocation
+		let other = SharedString::from(input.as_str());
+		assert_eq!(path.offset_in(&other), None);
+	}
+
+	#[test]
+	fn eq_ignore_case() {
+		let s = SharedString::from("Hello World");
+		assert!(s.eq_ignore_case("hello world"));
+		assert!(s.eq_ignore_case("HELLO WORLD"));
+		assert!(!s.eq_ignore_case("hello"));
+
+		let s = SharedString::from("\u{130}");
+		assert!(s.eq_ignore_case("i\u{307}"));
+		assert!(s.eq_ignore_case("I\u{307}"));
+		assert!(!s.eq_ignore_case("i"));
+
+		let s = SharedString::from("\u{3a3}");
+		assert!(s.eq_ignore_case("\u{3c3}"));
+		assert!(s.eq_ignore_case("\u{3c2}"));
+		assert!(SharedString::from("\u{3c3}").eq_ignore_case("\u{3c2}"));
+
+		assert!(SharedString::from("Straße").eq_ignore_case("STRASSE"));
+		assert!(SharedString::new().eq_ignore_case(""));
+	}
+
+	#[cfg(feature = "unicode")]
+	#[test]
+	fn graphemes() {
+		// thumbs up with skin tone modifier
+		let s = SharedString::from("\u{1f44d}\u{1f3fd}");
+		let mut graphemes = s.clone().graphemes();
+		assert_eq!(graphemes.next().unwrap(), s);
+		assert_eq!(graphemes.next(), None);
+
+		let s = SharedString::from("ye\u{301}s\u{1f44d}\u{1f3fd}!");
+		let graphemes: Vec<_> = s.clone().graphemes().collect();
+		assert_eq!(graphemes, ["y", "e\u{301}", "s", "\u{1f44d}\u{1f3fd}", "!"]);
+		assert_eq!(graphemes[3].as_full_str(), s.as_str());
+
+		let rev: Vec<_> = s.graphemes().rev().collect();
+		assert_eq!(rev, ["!", "\u{1f44d}\u{1f3fd}", "s", "e\u{301}", "y"]);
+
+		assert_eq!(SharedString::new().graphemes().next(), None);
+	}
+
+	#[test]
+	fn split_first_char() {
+		let s = SharedString::from("abc");
+		let (c, rest) = s.clone().split_first_char().unwrap();
+		assert_eq!(c, 'a');
+		assert_eq!(rest, "bc");
+		assert_eq!(rest.as_full_str(), "abc");
+		assert_eq!(rest.ref_count(), 2);
+
+		let (c, rest) = SharedString::from("€1").split_first_char().unwrap();
+		assert_eq!(c, '€');
+		assert_eq!(rest, "1");
+		let (c, rest) = rest.split_first_char().unwrap();
+		assert_eq!(c, '1');
+		assert!(rest.is_empty());
+		assert_eq!(rest.split_first_char(), None);
+
+		let s = SharedString::from("a😀b").idx(1..);
+		let (c, rest) = s.split_first_char().unwrap();
+		assert_eq!(c, '😀');
+		assert_eq!(rest, "b");
+	}
+
+	#[test]
+	fn char_at_and_nth_char() {
+		let s = SharedString::from("x€yä").idx(1..);
+		assert_eq!(s, "€yä");
+
+		assert_eq!(s.char_at(0), Some('€'));
+		assert_eq!(s.char_at(1), None);
+		assert_eq!(s.char_at(2), None);
+		assert_eq!(s.char_at(3), Some('y'));
+		assert_eq!(s.char_at(4), Some('ä'));
+		assert_eq!(s.char_at(5), None);
+		assert_eq!(s.char_at(6), None);
+		assert_eq!(s.char_at(100), None);
+
+		assert_eq!(s.nth_char(0), Some('€'));
+		assert_eq!(s.nth_char(1), Some('y'));
+		assert_eq!(s.nth_char(2), Some('ä'));
+		assert_eq!(s.nth_char(3), None);
+
+		assert_eq!(SharedString::new().char_at(0), None);
+		assert_eq!(SharedString::new().nth_char(0), None);
+	}
+
+	#[test]
+	fn byte_access() {
+		let s = SharedString::from("+OK\r\n").idx(1..3);
+		assert_eq!(s, "OK");
+		assert_eq!(s.byte(0), Some(b'O'));
+		assert_eq!(s.byte(1), Some(b'K'));
+		assert_eq!(s.byte(2), None);
+		assert_eq!(s.first_byte(), Some(b'O'));
+		assert_eq!(s.last_byte(), Some(b'K'));
+
+		let s = SharedString::from("ä");
+		assert_eq!(s.byte(1), Some(0xa4));
+
+		let empty = SharedString::from("foo").idx(3..);
+		assert_eq!(empty.byte(0), None);
+		assert_eq!(empty.first_byte(), None);
+		assert_eq!(empty.last_byte(), None);
+	}
+
+	#[test]
+	fn line_ranges() {
+		let input = SharedString::from("fn main() {\r\n\tfoo();\n}\n");
+		let lines: Vec<_> = input.clone().line_ranges().collect();
+		assert_eq!(lines.len(), 3);
+
+		assert_eq!(lines[0].0, 0..11);
+		assert_eq!(lines[1].0, 13..20);
+		assert_eq!(lines[2].0, 21..22);
+		for (range, line) in &lines {
+			assert_eq!(&input[range.clone()], line.as_str());
+		}
+
+		// ranges are relative to the string line_ranges was called on
+		let rest = input.idx(13..);
+		let ranges: Vec<_> = rest.line_ranges()
+			.map(|(r, _)| r)
+			.collect();
+		assert_eq!(ranges, [0..7, 8..9]);
+	}
+
+	#[test]
+	fn eq_same_bytes() {
+		let mut a = SharedString::from("foo bar foo");
+		let b = a.split_off(4);
+		let a_clone = a.clone();
+
+		assert_eq!(a, a_clone);
+		assert_eq!(a.idx(..3), b.idx(4..));
+		assert_ne!(a, b);
+		assert_ne!(a.idx(..3), a.idx(..2));
+
+		// different allocations still compare the bytes
+		let sync = SharedSyncString::from(a.as_str());
+		assert_eq!(a, sync);
+
+		// empty strings are always equal
+		assert_eq!(a.idx(1..1), b.idx(2..2));
+	}
+
+	#[test]
+	fn as_str_mut() {
+		let mut s = SharedString::from("foo bar");
+		s.as_str_mut().unwrap().make_ascii_uppercase();
+		assert_eq!(s, "FOO BAR");
+
+		let mut bar = s.split_off(4);
+		assert!(s.as_str_mut().is_none());
+		assert!(bar.as_str_mut().is_none());
+
+		drop(s);
+		bar.as_str_mut().unwrap().make_ascii_lowercase();
+		assert_eq!(bar, "bar");
+		assert_eq!(bar.as_full_str(), "FOO bar");
+
+		let mut sync = SharedSyncString::from("a");
+		let clone = sync.clone();
+		assert!(sync.as_str_mut().is_none());
+		drop(clone);
+		assert_eq!(sync.as_str_mut().map(|s| &*s), Some("a"));
+	}
+
+	#[test]
+	fn make_ascii_case() {
+		let mut s = SharedString::from("Hello Wörld");
+		let mut world = s.split_off(6);
+		let clone = world.clone();
+
+		world.make_ascii_uppercase();
+		assert_eq!(world, "WöRLD");
+		assert_eq!(clone, "Wörld");
+		assert_eq!(s.as_full_str(), "Hello Wörld");
+
+		// unique, so no copy is needed
+		let ptr = world.as_bytes().as_ptr();
+		world.make_ascii_lowercase();
+		assert_eq!(world, "wörld");
+		assert_eq!(world.as_bytes().as_ptr(), ptr);
+
+		// nothing to convert
+		let clone = s.clone();
+		s.make_ascii_uppercase();
+		s.make_ascii_uppercase();
+		assert_eq!(s, "HELLO ");
+		assert_eq!(clone, "Hello ");
+		assert_eq!(clone.as_full_str(), "Hello Wörld");
+	}
+
+	#[test]
+	fn into_bytes_iter() {
+		let s = SharedString::from("xäy!").idx(1..4);
+		let bytes: Vec<u8> = s.clone().into_bytes_iter().collect();
+		assert_eq!(bytes, s.as_bytes());
+
+		let mut iter = s.clone().into_bytes_iter();
+		assert_eq!(iter.len(), 3);
+		assert_eq!(iter.next_back(), Some(b'y'));
+		assert_eq!(iter.next(), Some(0xc3));
+		assert_eq!(iter.len(), 1);
+		assert_eq!(iter.next(), Some(0xa4));
+		assert_eq!(iter.next(), None);
+		assert_eq!(iter.next_back(), None);
+
+		assert_eq!(SharedString::new().into_bytes_iter().next(), None);
+	}
+
+	#[test]
+	fn chunks() {
+		let s = SharedString::from("0123456789");
+		let chunks: Vec<_> = s.clone().chunks(5).collect();
+		assert_eq!(chunks, ["01234", "56789"]);
+		let chunks: Vec<_> = s.clone().chunks(3).collect();
+		assert_eq!(chunks, ["012", "345", "678", "9"]);
+		let chunks: Vec<_> = s.clone().chunks(20).collect();
+		assert_eq!(chunks, ["0123456789"]);
+		assert_eq!(chunks[0].as_full_str(), s.as_str());
+
+		// € has 3 bytes
+		let s = SharedString::from("a€bc€");
+		let chunks: Vec<_> = s.clone().chunks(3).collect();
+		assert_eq!(chunks, ["a", "€", "bc", "€"]);
+		let chunks: Vec<_> = s.clone().chunks(4).collect();
+		assert_eq!(chunks, ["a€", "bc", "€"]);
+		let chunks: Vec<_> = s.clone().chunks(1).collect();
+		assert_eq!(chunks, ["a", "€", "b", "c", "€"]);
+
+		assert_eq!(SharedString::new().chunks(2).next(), None);
+
+		let s = SharedString::from("0123456789");
+		assert_eq!(s.clone().chunks(3).size_hint(), (4, Some(10)));
+		assert_eq!(s.clone().chunks(5).size_hint(), (2, Some(10)));
+		assert_eq!(s.clone().rchunks(usize::MAX).size_hint(), (1, Some(10)));
+		assert_eq!(SharedString::new().chunks(2).size_hint(), (0, Some(0)));
+	}
+
+	#[test]
+	fn rchunks() {
+		let s = SharedString::from("0123456789");
+		let chunks: Vec<_> = s.clone().rchunks(3).collect();
+		assert_eq!(chunks, ["789", "456", "123", "0"]);
+
+		let s = SharedString::from("€ab€c");
+		let chunks: Vec<_> = s.clone().rchunks(3).collect();
+		assert_eq!(chunks, ["c", "€", "ab", "€"]);
+		let chunks: Vec<_> = s.clone().rchunks(4).collect();
+		assert_eq!(chunks, ["€c", "ab", "€"]);
+		let chunks: Vec<_> = s.rchunks(1).collect();
+		assert_eq!(chunks, ["c", "€", "b", "a", "€"]);
+
+		assert_eq!(SharedString::new().rchunks(2).next(), None);
+	}
+
+	#[test]
+	#[should_panic]
+	fn chunks_zero_size() {
+		let _ = SharedString::from("a").chunks(0);
+	}
+
+	#[test]
+	fn line_windows() {
+		let s = SharedString::from("1\n2\r\n3\n4\n5\n");
+		let windows: Vec<_> = s.clone().line_windows(2).collect();
+		assert_eq!(windows, [
+			["1", "2"],
+			["2", "3"],
+			["3", "4"],
+			["4", "5"]
+		]);
+		assert_eq!(windows[3][1].as_full_str(), s.as_str());
+
+		let windows: Vec<_> = s.clone().line_windows(5).collect();
+		assert_eq!(windows, [["1", "2", "3", "4", "5"]]);
+
+		assert_eq!(s.clone().line_windows(6).next(), None);
+		assert_eq!(s.line_windows(1).count(), 5);
+		assert_eq!(SharedString::new().line_windows(1).next(), None);
+	}
+
+	#[test]
+	fn strip_bom() {
+		let s = SharedString::from("\u{feff}foo");
+		let stripped = s.strip_bom();
+		assert_eq!(stripped, "foo");
+		assert_eq!(stripped.as_full_str(), s.as_str());
+
+		let s = SharedString::from("foo\u{feff}");
+		assert_eq!(s.strip_bom(), "foo\u{feff}");
+
+		// only one bom is removed
+		let s = SharedString::from("\u{feff}\u{feff}");
+		assert_eq!(s.strip_bom(), "\u{feff}");
+
+		assert_eq!(SharedString::new().strip_bom(), "");
+	}
+
+	#[test]
+	fn from_utf16() {
+		let ascii: Vec<u16> = "foo bar".encode_utf16().collect();
+		let s = SharedString::from_utf16(&ascii).unwrap();
+		assert_eq!(s, "foo bar");
+		assert_eq!(SharedString::from_utf16_lossy(&ascii), "foo bar");
+
+		// surrogate pair
+		let emoji = [0xD83D, 0xDE00];
+		assert_eq!(SharedString::from_utf16(&emoji).unwrap(), "😀");
+		assert_eq!(SharedString::from_utf16_lossy(&emoji), "😀");
+
+		// lone surrogate
+		let lone = [0x0061, 0xDE00, 0x0062];
+		assert!(SharedString::from_utf16(&lone).is_err());
+		assert_eq!(SharedString::from_utf16_lossy(&lone), "a\u{FFFD}b");
+
+		assert_eq!(SharedString::from_utf16(&[]).unwrap(), "");
+	}
+
+	#[cfg(feature = "regex")]
+	#[test]
+	fn split_regex() {
+		use regex::Regex;
+
+		let re = Regex::new(r"\s+").unwrap();
+		let s = SharedString::from("foo  bar\t\nbaz");
+		let split: Vec<_> = s.clone().split_regex(&re).collect();
+		assert_eq!(split, ["foo", "bar", "baz"]);
+		assert_eq!(split[2].as_full_str(), s.as_str());
+
+		let s = SharedString::from(" ä  ö ");
+		let split: Vec<_> = s.split_regex(&re).collect();
+		assert_eq!(split, ["", "ä", "ö"]);
+
+		let s = SharedString::from("xx foo bar").idx(3..);
+		let split: Vec<_> = s.split_regex(&re).collect();
+		assert_eq!(split, ["foo", "bar"]);
+
+		let no_match = SharedString::from("foo");
+		let split: Vec<_> = no_match.split_regex(&re).collect();
+		assert_eq!(split, ["foo"]);
+
+		assert_eq!(SharedString::new().split_regex(&re).next(), None);
+
+		// empty matches
+		let re = Regex::new(r"").unwrap();
+		let split: Vec<_> = SharedString::from("aä")
+			.split_regex(&re)
+			.collect();
+		assert_eq!(split, ["", "a", "ä"]);
+
+		let re = Regex::new(r"x*").unwrap();
+		let split: Vec<_> = SharedString::from("axxb")
+			.split_regex(&re)
+			.collect();
+		let std: Vec<_> = re.split("axxb").collect();
+		assert_eq!(split, ["", "a", "b"]);
+		assert_eq!(std, ["", "a", "b", ""]);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn from_reader_all() {
+		use std::io::{self, Read};
+
+		let reader = io::BufReader::with_capacity(4, &b"a=1\nb=2\n"[..]);
+		let s = SharedString::from_reader_all(reader).unwrap();
+		let lines: Vec<_> = s.clone().lines().collect();
+		assert_eq!(lines, ["a=1", "b=2"]);
+		assert_eq!(lines[1].as_full_str(), s.as_str());
+
+		let reader = (&b"foo "[..]).chain(&b"\xff"[..]);
+		let e = SharedString::from_reader_all(reader).unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn split_off_floor() {
+		// € has 3 bytes
+		for at in 1..4 {
+			let mut s = SharedString::from("a€b");
+			let rest = s.split_off_floor(at);
+			assert_eq!(s, "a", "at {}", at);
+			assert_eq!(rest, "€b", "at {}", at);
+		}
+
+		let mut s = SharedString::from("a€b");
+		let rest = s.split_off_floor(4);
+		assert_eq!(s, "a€");
+		assert_eq!(rest, "b");
+
+		let mut s = SharedString::from("a€b");
+		let rest = s.split_off_floor(5);
+		assert_eq!(s, "a€b");
+		assert_eq!(rest, "");
+		assert_eq!(rest.as_full_str(), "a€b");
+
+		let mut s = SharedString::from("€");
+		let rest = s.split_off_floor(2);
+		assert_eq!(s, "");
+		assert_eq!(rest, "€");
+	}
+
+	#[test]
+	#[should_panic]
+	fn split_off_floor_out_of_bounds() {
+		let mut s = SharedString::from("a€b");
+		let _ = s.split_off_floor(6);
+	}
+
+	#[test]
+	fn idx_unchecked() {
+		let s = SharedString::from("a€b");
+		let euro = unsafe { s.idx_unchecked(1..4) };
+		assert_eq!(euro, "€");
+		assert_eq!(unsafe { euro.idx_unchecked(..) }, "€");
+		assert_eq!(unsafe { s.idx_unchecked(4..=4) }, "b");
+		assert_eq!(unsafe { s.idx_unchecked(5..) }, "");
+	}
+
+	#[cfg(debug_assertions)]
+	#[test]
+	#[should_panic(expected = "range not at a char boundary")]
+	fn idx_unchecked_not_char_boundary() {
+		let s = SharedString::from("a€b");
+		let _ = unsafe { s.idx_unchecked(2..) };
+	}
+
+	#[cfg(debug_assertions)]
+	#[test]
+	#[should_panic(expected = "range out-of-bounds")]
+	fn idx_unchecked_out_of_bounds() {
+		let s = SharedString::from("a€b").idx(..4);
+		let _ = unsafe { s.idx_unchecked(..5) };
+	}
+
+	#[test]
+	fn reverse() {
+		let s = SharedString::from("foo bar");
+		assert_eq!(s.reverse(), "rab oof");
+		assert_eq!(s.reverse().reverse(), s);
+
+		let s = SharedString::from("x€好😀").idx(1..);
+		let rev = s.reverse();
+		assert_eq!(rev, "😀好€");
+		assert_eq!(rev.len(), s.len());
+		assert_eq!(rev.as_full_str(), "😀好€");
+
+		// reverses chars not graphemes
+		let s = SharedString::from("e\u{301}");
+		assert_eq!(s.reverse(), "\u{301}e");
+
+		assert_eq!(SharedString::new().reverse(), "");
+	}
+
+	#[test]
+	fn collect_string() {
+		let s = SharedString::from("the  quick brown fox");
+		let joined = SharedString::collect_string(s.clone().split(b' '), " ");
+		assert_eq!(joined, s);
+
+		let joined = SharedString::collect_string(s.clone().split(b' '), "");
+		assert_eq!(joined, "thequickbrownfox");
+
+		// a single part is not copied
+		let joined = SharedString::collect_string(s.clone().split(b'x'), ",");
+		assert_eq!(joined, "the  quick brown fo");
+		assert_eq!(joined.as_full_str(), s.as_str());
+
+		let empty = SharedString::collect_string(Vec::new(), ",");
+		assert!(empty.is_empty());
+
+		// a lower bound of usize::MAX doesn't overflow the capacity
+		struct Lying(std::vec::IntoIter<SharedString>);
+		impl Iterator for Lying {
+			type Item = SharedString;
+			fn next(&mut self) -> Option<SharedString> {
+				self.0.next()
+			}
+			fn size_hint(&self) -> (usize, Option<usize>) {
+				(usize::MAX, None)
+			}
+		}
+
+		let parts = vec!["a".into(), "b".into(), "c".into()];
+		let joined = SharedString::collect_string(Lying(parts.into_iter()), ",");
+		assert_eq!(joined, "a,b,c");
+	}
+
+	#[test]
+	fn cmp_all_operand_orders() {
+		use std::cmp::Ordering;
+
+		let values = ["", "a", "ab", "b", "ä", "\u{10000}"];
+		for a in values.iter() {
+			for b in values.iter() {
+				let expected = a.cmp(b);
+				let eq = expected == Ordering::Equal;
+				let (a, b) = (*a, *b);
+				let shared = SharedString::from(a);
+				let sync = SharedSyncString::from(b);
+				let other = SharedString::from(b);
+				let string = b.to_string();
+
+				assert_eq!(shared == sync, eq);
+				assert_eq!(shared == other, eq);
+				assert_eq!(shared == *b, eq);
+				assert_eq!(shared == b, eq);
+				assert_eq!(shared == string, eq);
+				assert_eq!(*b == shared, eq);
+				assert_eq!(b == shared, eq);
+				assert_eq!(string == shared, eq);
+
+				let ord = Some(expected);
+				let rev = Some(expected.reverse());
+				assert_eq!(shared.partial_cmp(&sync), ord);
+				assert_eq!(shared.partial_cmp(&other), ord);
+				assert_eq!(shared.cmp(&other), expected);
+				assert_eq!(shared.partial_cmp(b), ord);
+				assert_eq!(shared.partial_cmp(&b), ord);
+				assert_eq!(shared.partial_cmp(&string), ord);
+				assert_eq!(b.partial_cmp(&shared), rev);
+				assert_eq!((&b).partial_cmp(&shared), rev);
+				assert_eq!(string.partial_cmp(&shared), rev);
+				assert_eq!(string.partial_cmp(&SharedSyncString::from(a)), rev);
+			}
+		}
+	}
+
+	#[test]
+	fn split_indices() {
+		let s = SharedString::from("a,bb,ccc");
+		let split: Vec<_> = s.clone().split_indices(b',').collect();
+		assert_eq!(split, [
+			(0, "a".into()),
+			(2, "bb".into()),
+			(5, "ccc".into())
+		]);
+		for (i, segment) in &split {
+			assert_eq!(&s[*i..(i + segment.len())], segment.as_str());
+		}
+
+		let rev: Vec<_> = s.clone().split_indices(b',').rev().collect();
+		assert_eq!(rev[0], (5, "ccc".into()));
+		assert_eq!(rev[2], (0, "a".into()));
+
+		// relative to the string split_indices was called on
+		let split: Vec<_> = s.idx(2..).split_indices(b',').collect();
+		assert_eq!(split, [(0, "bb".into()), (3, "ccc".into())]);
+
+		let split: Vec<_> = SharedString::from(",a,").split_indices(b',')
+			.collect();
+		assert_eq!(split, [(0, "".into()), (1, "a".into())]);
+	}
+
+	#[test]
+	fn rsplit_once_str() {
+		let path = SharedString::from("crate::iter::Split");
+		let (module, name) = path.rsplit_once_str("::").unwrap();
+		assert_eq!(module, "crate::iter");
+		assert_eq!(name, "Split");
+		assert_eq!(name.as_full_str(), path.as_str());
+
+		let (parent, module) = module.rsplit_once_str("::").unwrap();
+		assert_eq!(parent, "crate");
+		assert_eq!(module, "iter");
+		assert_eq!(parent.rsplit_once_str("::"), None);
+
+		let (a, b) = SharedString::from("a::").rsplit_once_str("::").unwrap();
+		assert_eq!(a, "a");
+		assert_eq!(b, "");
+
+		// overlapping candidates
+		let (a, b) = SharedString::from("a:::b").rsplit_once_str("::").unwrap();
+		assert_eq!(a, "a:");
+		assert_eq!(b, "b");
+
+		let (a, b) = SharedString::from("ä→ö").rsplit_once_str("→").unwrap();
+		assert_eq!(a, "ä");
+		assert_eq!(b, "ö");
+	}
+
+	#[test]
+	fn first_line() {
+		let s = SharedString::from("HTTP/1.1 200 OK\r\nHeader: v");
+		assert_eq!(s.first_line(), "HTTP/1.1 200 OK");
+		assert_eq!(s.rest_after_first_line(), "Header: v");
+		assert_eq!(s.first_line().as_full_str(), s.as_str());
+		assert_eq!(s.first_line(), s.clone().lines().next().unwrap());
+
+		let rest = s.rest_after_first_line();
+		assert_eq!(rest.first_line(), "Header: v");
+		assert_eq!(rest.rest_after_first_line(), "");
+
+		let s = SharedString::from("a\nb\r\nc");
+		assert_eq!(s.first_line(), "a");
+		assert_eq!(s.rest_after_first_line(), "b\r\nc");
+
+		// a \r without \n is kept
+		let s = SharedString::from("a\r");
+		assert_eq!(s.first_line(), "a\r");
+		assert_eq!(s.rest_after_first_line(), "");
+
+		let s = SharedString::from("\r\n");
+		assert_eq!(s.first_line(), "");
+		assert_eq!(s.rest_after_first_line(), "");
+		assert_eq!(SharedString::new().first_line(), "");
+	}
+
+	#[test]
+	fn is_ascii() {
+		let s = SharedString::from("GET /ä");
+		assert!(!s.is_ascii());
+		assert!(s.idx(..5).is_ascii());
+		assert!(SharedString::new().is_ascii());
+
+		// every index of an ascii string is valid
+		let ascii = s.idx(..5);
+		for i in 0..=ascii.len() {
+			assert!(ascii.get(i..).is_some());
+			assert!(ascii.get(..i).is_some());
+		}
+		assert!(ascii.get(..6).is_none());
+
+		// the ä starts at 5
+		assert!(s.get(6..).is_none());
+	}
+
+	#[test]
+	fn arc_str() {
+		use std::sync::Arc;
+
+		let arc: Arc<str> = Arc::from("foo bär");
+		let s = SharedString::from(arc.clone());
+		assert_eq!(s, "foo bär");
+
+		let back = s.clone().into_arc_str();
+		assert_eq!(back, arc);
+
+		let sync: SharedSyncString = arc.clone().into();
+		let back: Arc<str> = sync.idx(4..).into();
+		assert_eq!(&*back, "bär");
+
+		let empty = SharedString::from(Arc::<str>::from(""));
+		assert!(empty.is_empty());
+		assert_eq!(&*empty.into_arc_str(), "");
+	}
+
+	#[test]
+	fn matches() {
+		let s = SharedString::from("aaaaa");
+		let v: Vec<_> = s.clone().matches("aa").collect();
+		assert_eq!(v, ["aa", "aa"]);
+		assert_eq!(v[1].as_full_str(), "aaaaa");
+
+		let s = SharedString::from("xabcabcyabc");
+		let v: Vec<_> = s.idx(1..).matches("abc").collect();
+		assert_eq!(v, ["abc", "abc", "abc"]);
+
+		assert_eq!(s.clone().matches("z").count(), 0);
+		assert_eq!(SharedString::new().matches("a").count(), 0);
+	}
+
+	#[test]
+	#[should_panic]
+	fn matches_empty_pattern() {
+		let _ = SharedString::from("abc").matches("");
+	}
+
+	#[test]
+	fn split_at_first_whitespace() {
+		let s = SharedString::from("GET   /index.html HTTP/1.1");
+		let (cmd, args) = s.split_at_first_whitespace().unwrap();
+		assert_eq!(cmd, "GET");
+		assert_eq!(args, "/index.html HTTP/1.1");
+		assert_eq!(args.as_full_str(), s.as_str());
+
+		let s = SharedString::from("PING\t\t \tnow");
+		let (cmd, args) = s.split_at_first_whitespace().unwrap();
+		assert_eq!(cmd, "PING");
+		assert_eq!(args, "now");
+
+		let s = SharedString::from("QUIT \n");
+		let (cmd, args) = s.split_at_first_whitespace().unwrap();
+		assert_eq!(cmd, "QUIT");
+		assert!(args.is_empty());
+
+		let s = SharedString::from(" lead");
+		let (cmd, args) = s.split_at_first_whitespace().unwrap();
+		assert!(cmd.is_empty());
+		assert_eq!(args, "lead");
+
+		assert!(SharedString::from("QUIT").split_at_first_whitespace().is_none());
+		assert!(SharedString::new().split_at_first_whitespace().is_none());
+	}
+
+	#[test]
+	fn char_boundary() {
+		// a: 0, ä: 1..3, 好: 3..6, b: 6
+		let s = SharedString::from("aä好b");
+		let floor: Vec<_> = (0..=8).map(|i| s.floor_char_boundary(i)).collect();
+		assert_eq!(floor, [0, 1, 1, 3, 3, 3, 6, 7, 7]);
+		let ceil: Vec<_> = (0..=8).map(|i| s.ceil_char_boundary(i)).collect();
+		assert_eq!(ceil, [0, 1, 3, 3, 6, 6, 6, 7, 7]);
+
+		// relative to the substring
+		let sub = s.idx(1..6);
+		assert_eq!(sub.floor_char_boundary(1), 0);
+		assert_eq!(sub.ceil_char_boundary(1), 2);
+
+		let e = SharedString::new();
+		assert_eq!(e.floor_char_boundary(3), 0);
+		assert_eq!(e.ceil_char_boundary(0), 0);
+	}
+
+	#[test]
+	fn trim() {
+		let s = SharedString::from(" \t\u{00A0}foo bar\u{00A0}\t \r\n");
+		assert_eq!(s.trim(), "foo bar");
+		assert_eq!(s.trim_start(), "foo bar\u{00A0}\t \r\n");
+		assert_eq!(s.trim_end(), " \t\u{00A0}foo bar");
+		assert_eq!(s.trim().as_full_str(), s.as_str());
+
+		let empty = SharedString::from(" \t ");
+		assert!(empty.trim().is_empty());
+		assert!(empty.trim_start().is_empty());
+		assert!(empty.trim_end().is_empty());
+	}
+
+	#[test]
+	fn trim_ascii() {
+		let s = SharedString::from("\r\n\t foo\tbar \x0c\r\n");
+		assert_eq!(s.trim_ascii(), "foo\tbar");
+		assert_eq!(s.trim_ascii_start(), "foo\tbar \x0c\r\n");
+		assert_eq!(s.trim_ascii_end(), "\r\n\t foo\tbar");
+		assert_eq!(s.trim_ascii().as_full_str(), s.as_str());
+
+		// only ascii whitespace is removed
+		let s = SharedString::from("\u{a0}foo\u{3000}\x0b");
+		assert_eq!(s.trim_ascii(), s);
+		assert_eq!(s.trim(), "foo");
+
+		let s = SharedString::from(" ä ");
+		assert_eq!(s.trim_ascii(), "ä");
+
+		let s = SharedString::from(" \t\r\n");
+		assert_eq!(s.trim_ascii(), "");
+		assert_eq!(s.trim_ascii_start(), "");
+		assert_eq!(s.trim_ascii_end(), "");
+		assert_eq!(SharedString::new().trim_ascii(), "");
+	}
+
+	#[test]
+	fn strip_prefix_suffix() {
+		let s = SharedString::from("Bearer abc");
+		assert_eq!(s.strip_prefix("Bearer ").unwrap(), "abc");
+		assert_eq!(s.strip_suffix("abc").unwrap(), "Bearer ");
+		assert_eq!(s.strip_prefix("").unwrap(), s);
+		assert_eq!(s.strip_suffix("").unwrap(), s);
+		assert!(s.strip_prefix("Bearer abc and more").is_none());
+		assert!(s.strip_suffix("more Bearer abc").is_none());
+		assert!(s.strip_prefix("abc").is_none());
+	}
+
+	#[test]
+	fn ord() {
+		let raw = ["foo", "bar", "", "baz", "Foo", "好", "ba"];
+		let mut strs = raw.to_vec();
+		let mut shared: Vec<_> = raw.iter()
+			.map(|s| SharedString::from(*s))
+			.collect();
+
+		strs.sort();
+		shared.sort();
+		assert_eq!(shared, strs);
+
+		let bar = SharedString::from("bar");
+		let arc = SharedSyncString::from("baz");
+		assert!(bar < arc);
+		assert!(bar < *"baz");
+		assert!(*"baz" > bar);
+		assert!(*"bar" <= bar);
+	}
+
+	#[test]
+	fn ref_count() {
+		let mut foo = SharedString::from("foo bar baz");
+		assert!(foo.is_unique());
+		assert_eq!(foo.ref_count(), 1);
+
+		let clone = foo.clone();
+		assert_eq!(foo.ref_count(), 2);
+
+		let bar = foo.split_off(4);
+		assert_eq!(bar.ref_count(), 3);
+		assert!(!bar.is_unique());
+
+		drop(clone);
+		drop(foo);
+		assert!(bar.is_unique());
+
+		let lines: Vec<_> = SharedSyncString::from("a\nb").lines().collect();
+		assert_eq!(lines[0].ref_count(), 2);
+	}
+
+	#[test]
+	fn to_mut() {
+		// unique
+		let mut foo = SharedString::from("foobar");
+		let ptr = foo.as_full_bytes().as_ptr();
+		foo.truncate(3);
+		foo.to_mut().make_ascii_uppercase();
+		assert_eq!(foo, "FOO");
+		assert_eq!(foo.as_full_bytes().as_ptr(), ptr);
+		assert_eq!(foo.as_full_str(), "FOObar");
+
+		// shared
+		let mut foo = SharedString::from("foobar");
+		let ptr = foo.as_full_bytes().as_ptr();
+		let bar = foo.split_off(3);
+		let mut bar_clone = bar.clone();
+		bar_clone.to_mut().make_ascii_uppercase();
+		assert_eq!(bar_clone, "BAR");
+		assert_eq!(bar_clone.as_full_str(), "BAR");
+		assert_ne!(bar_clone.as_full_bytes().as_ptr(), ptr);
+		assert_eq!(bar, "bar");
+		assert_eq!(foo.as_full_str(), "foobar");
+	}
+
+	#[test]
+	fn push_str() {
+		use std::rc::Rc;
+
+		// unique, the reference counter is reused
+		let mut foo = SharedString::from("foo");
+		let rc = Rc::as_ptr(&foo.bytes);
+		foo.push_str("bar");
+		foo.push('!');
+		assert_eq!(foo, "foobar!");
+		assert!(foo.is_unique());
+		assert_eq!(Rc::as_ptr(&foo.bytes), rc);
+
+		// unique after split
+		let mut foo = SharedString::from("foobar");
+		let mut bar = foo.split_off(3);
+		drop(foo);
+		let rc = Rc::as_ptr(&bar.bytes);
+		bar.push('好');
+		assert_eq!(bar, "bar好");
+		assert_eq!(bar.as_full_str(), "bar好");
+		assert_eq!(Rc::as_ptr(&bar.bytes), rc);
+
+		// shared
+		let mut foo = SharedString::from("foobar");
+		let bar = foo.split_off(3);
+		foo.push_str("baz");
+		assert_eq!(foo, "foobaz");
+		assert_eq!(bar, "bar");
+		assert_eq!(bar.as_full_str(), "foobar");
+		assert!(foo.is_unique());
+	}
+
+	#[test]
+	fn ascii_case() {
+		let s = SharedString::from("Grüße, WORLD!");
+		assert_eq!(s.to_ascii_lowercase(), "grüße, world!");
+		assert_eq!(s.to_ascii_uppercase(), "GRüßE, WORLD!");
+		assert!(s.eq_ignore_ascii_case("grüße, world!"));
+		assert!(!s.eq_ignore_ascii_case("GRÜSSE, WORLD!"));
+	}
+
+	#[test]
+	fn unicode_case() {
+		let inputs = ["İstanbul", "Straße", "ΣΑΣ", "ὈΔΥΣΣΕΎΣ", "Hello"];
+		for input in &inputs {
+			let s = SharedString::from(*input);
+			assert_eq!(s.to_lowercase(), input.to_lowercase().as_str());
+			assert_eq!(s.to_uppercase(), input.to_uppercase().as_str());
+		}
+
+		let s = SharedString::from("ß");
+		assert_eq!(s.to_uppercase(), "SS");
+		assert_eq!(s.to_uppercase().len(), 2);
+
+		// final sigma
+		assert_eq!(SharedString::from("ΣΑΣ").to_lowercase(), "σας");
+		assert_eq!(SharedString::from("İ").to_lowercase(), "i\u{307}");
+	}
+
+	#[test]
+	fn fused() {
+		fn assert_fused<I: std::iter::FusedIterator>(_: &I) {}
+
+		let mut split = SharedString::from("a b").split(b' ');
+		assert_fused(&split);
+		assert_eq!(split.by_ref().count(), 2);
+		for _ in 0..3 {
+			assert_eq!(split.next(), None);
+		}
+
+		let mut lines = SharedString::from("a\nb\n").lines();
+		assert_fused(&lines);
+		assert_eq!(lines.by_ref().count(), 2);
+		for _ in 0..3 {
+			assert_eq!(lines.next(), None);
+		}
+	}
+
+	#[test]
+	fn size_hint() {
+		let split = SharedString::from("a b  c").split(b' ');
+		assert_eq!(split.size_hint(), (1, Some(6)));
+		assert_eq!(split.count(), 4);
+
+		let split = SharedString::from("   ").split(b' ');
+		assert_eq!(split.size_hint(), (1, Some(3)));
+		assert_eq!(split.count(), 3);
+
+		let lines = SharedString::from("a\r\nb").lines();
+		assert_eq!(lines.size_hint(), (1, Some(4)));
+
+		let mut lines = SharedString::from("a\nb").lines();
+		lines.next();
+		lines.next();
+		assert_eq!(lines.size_hint(), (0, Some(0)));
+	}
+
+	#[test]
+	fn split_whitespace() {
+		let inputs = [
+			"", "   ", "a", " a b ", "a  \t b\n\nc", "\t\u{00A0}好 \r\n 好\t"
+		];
+
+		for input in &inputs {
+			let split: Vec<_> = SharedString::from(*input)
+				.split_whitespace()
+				.collect();
+			let std: Vec<_> = input.split_whitespace().collect();
+			assert_eq!(split, std, "input {:?}", input);
+		}
+	}
+
+	#[test]
+	fn rsplit() {
+		let s = SharedString::from("a.b.c");
+		let forward: Vec<_> = s.clone().split(b'.').collect();
+		let backward: Vec<_> = s.clone().rsplit(b'.').collect();
+		assert_eq!(forward, ["a", "b", "c"]);
+		assert_eq!(backward, ["c", "b", "a"]);
+
+		let both: Vec<_> = s.rsplit(b'.').rev().collect();
+		assert_eq!(both, forward);
+	}
+
+	#[test]
+	fn rsplitn() {
+		let s = SharedString::from("archive.tar.gz");
+
+		assert_eq!(s.clone().rsplitn(0, b'.').next(), None);
+
+		let all: Vec<_> = s.clone().rsplitn(1, b'.').collect();
+		assert_eq!(all, ["archive.tar.gz"]);
+
+		let all: Vec<_> = s.clone().rsplitn(2, b'.').collect();
+		assert_eq!(all, ["gz", "archive.tar"]);
+
+		let all: Vec<_> = s.clone().rsplitn(10, b'.').collect();
+		assert_eq!(all, ["gz", "tar", "archive"]);
+
+		let all: Vec<_> = SharedString::from(".a").rsplitn(2, b'.').collect();
+		assert_eq!(all, ["a", ""]);
+
+		let all: Vec<_> = SharedString::from("a.").rsplitn(1, b'.').collect();
+		assert_eq!(all, ["a."]);
+	}
+
+	#[test]
+	fn try_split_off() {
+		use super::SplitError;
+
+		let mut s = SharedString::from("abc 好 def");
+		assert_eq!(s.try_split_off(5), Err(SplitError::NotCharBoundary));
+		assert_eq!(s.try_split_off(20), Err(SplitError::OutOfBounds));
+		assert_eq!(s, "abc 好 def");
+
+		let def = s.try_split_off(8).unwrap();
+		assert_eq!(s, "abc 好 ");
+		assert_eq!(def, "def");
+
+		let all = s.try_split_off(0).unwrap();
+		assert_eq!(all, "abc 好 ");
+		assert!(s.is_empty());
+	}
+
+	#[test]
+	fn split_at() {
+		let s = SharedString::from("ab好cd");
+		for at in &[0, 1, 2, 5, 7] {
+			let (head, tail) = s.split_at(*at);
+			let mut n_head = s.clone();
+			let n_tail = n_head.split_off(*at);
+			assert_eq!(head, n_head);
+			assert_eq!(tail, n_tail);
+		}
+
+		assert!(s.split_at_checked(3).is_none());
+		assert!(s.split_at_checked(8).is_none());
+		assert_eq!(s, "ab好cd");
+	}
+
+	#[test]
+	#[should_panic]
+	fn split_at_char_boundary() {
+		let _ = SharedString::from("好").split_at(1);
+	}
+
+	#[test]
+	fn full_bytes() {
+		let raw = SharedString::from("foobarbaz");
+		let bar = raw.idx(3..6);
+		assert_eq!(bar.as_bytes(), b"bar");
+		assert_eq!(bar.as_full_bytes(), b"foobarbaz");
+
+		let mut foo = raw.get(..6).unwrap();
+		let ba = foo.split_off(3).idx(..2);
+		assert_eq!(ba, "ba");
+		assert_eq!(ba.as_full_bytes(), b"foobarbaz");
+		assert_eq!(foo.as_full_str(), "foobarbaz");
+	}
+
+	#[test]
+	fn from_cow_and_box() {
+		use std::borrow::Cow;
+
+		let borrowed: Cow<'_, str> = Cow::Borrowed("foo");
+		assert_eq!(SharedString::from(borrowed), "foo");
+
+		let owned: Cow<'_, str> = Cow::Owned("bar".to_string());
+		assert_eq!(SharedString::from(owned), "bar");
+
+		let boxed: Box<str> = "baz".into();
+		let ptr = boxed.as_ptr();
+		let baz = SharedString::from(boxed);
+		assert_eq!(baz, "baz");
+		assert_eq!(baz.as_full_bytes().as_ptr(), ptr);
+	}
+
+	#[test]
+	fn from_ref() {
+		fn into_shared(s: impl Into<SharedString>) -> SharedString {
+			s.into()
+		}
+
+		let existing = SharedString::from("foo");
+		let n_existing = into_shared(&existing);
+		assert_eq!(n_existing, existing);
+		assert_eq!(existing.ref_count(), 2);
+		assert_eq!(
+			existing.as_full_bytes().as_ptr(),
+			n_existing.as_full_bytes().as_ptr()
+		);
+	}
+
+	#[test]
+	fn try_from_bytes() {
+		use std::convert::TryFrom;
+
+		let foo = SharedString::try_from(b"foo".to_vec()).unwrap();
+		assert_eq!(foo, "foo");
+
+		let bytes: Box<[u8]> = "好".as_bytes().into();
+		let ptr = bytes.as_ptr();
+		let s = SharedString::try_from(bytes).unwrap();
+		assert_eq!(s, "好");
+		assert_eq!(s.as_full_bytes().as_ptr(), ptr);
+
+		// invalid continuation byte
+		let invalid = vec![b'a', 0xe5, 0x28, 0xbd];
+		let e = SharedString::try_from(invalid.clone()).unwrap_err();
+		assert_eq!(e.utf8_error().valid_up_to(), 1);
+
+		let e = SharedString::try_from(invalid.into_boxed_slice()).unwrap_err();
+		assert_eq!(e.valid_up_to(), 1);
+	}
+
+	#[test]
+	fn from_utf8_lossy() {
+		let vec = "foo 好".as_bytes().to_vec();
+		let ptr = vec.as_ptr();
+		let s = SharedString::from_utf8_lossy(vec);
+		assert_eq!(s, "foo 好");
+		assert_eq!(s.as_full_bytes().as_ptr(), ptr);
+
+		let s = SharedString::from_utf8_lossy(vec![b'a', 0xff, b'b', 0xe5]);
+		assert_eq!(s, "a\u{FFFD}b\u{FFFD}");
+	}
+
+	#[test]
+	fn eq_string_and_cow() {
+		use std::borrow::Cow;
+
+		let line = SharedString::from("key: value");
+		let key = line.idx(..3);
+		let n_key = format!("{}{}", "ke", 'y');
+		assert_eq!(key, n_key);
+		assert_eq!(n_key, key);
+		assert_ne!(line, n_key);
+
+		let sync = SharedSyncString::from("key");
+		assert_eq!(sync, n_key);
+		assert_eq!(n_key, sync);
+
+		let cow: Cow<'_, str> = Cow::Borrowed("key");
+		assert_eq!(key, cow);
+		assert_eq!(cow, key);
+		assert_eq!(cow, sync);
+		assert_eq!(sync, cow);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn as_ref_bytes_and_path() {
+		use std::path::Path;
+
+		fn bytes(b: impl AsRef<[u8]>) -> Vec<u8> {
+			b.as_ref().to_vec()
+		}
+
+		fn extension(p: impl AsRef<Path>) -> Option<String> {
+			p.as_ref()
+				.extension()
+				.map(|e| e.to_string_lossy().into_owned())
+		}
+
+		let path = SharedString::from("dir/file.rs");
+		assert_eq!(bytes(path.clone()), path.as_bytes());
+		assert_eq!(extension(path.clone()).unwrap(), "rs");
+		assert!(!Path::exists(path.as_ref()));
 	}
-}
 
-// need a custom eq
-impl<R, O> cmp::PartialEq<SharedGenString<O>> for SharedGenString<R>
-where
-	R: RefCounter,
-	O: RefCounter {
-	#[inline]
-	fn eq(&self, other: &SharedGenString<O>) -> bool {
-		self.as_bytes() == other.as_bytes()
+	#[test]
+	fn default() {
+		#[derive(Default)]
+		struct Header {
+			key: SharedString,
+			value: SharedSyncString
+		}
+
+		assert!(SharedString::default().is_empty());
+		assert_eq!(SharedString::default(), SharedString::new());
+
+		let mut header = Header::default();
+		assert!(header.key.is_empty());
+		assert!(header.value.is_empty());
+
+		header.key = "foo".into();
+		let key = std::mem::take(&mut header.key);
+		assert_eq!(key, "foo");
+		assert!(header.key.is_empty());
 	}
-}
 
-impl<R: RefCounter> cmp::Eq for SharedGenString<R> {}
+	#[test]
+	fn from_str() {
+		fn parse_all<T: std::str::FromStr>(s: &str) -> Vec<T> {
+			s.split(',').filter_map(|p| p.parse().ok()).collect()
+		}
 
-impl<R> cmp::PartialEq<str> for SharedGenString<R>
-where R: RefCounter {
-	#[inline]
-	fn eq(&self, other: &str) -> bool {
-		self.as_str() == other
+		let foo: SharedString = "foo".parse().unwrap();
+		assert_eq!(foo, "foo");
+
+		let all: Vec<SharedSyncString> = parse_all("a,b,c");
+		assert_eq!(all, ["a", "b", "c"]);
 	}
-}
 
-impl<R> cmp::PartialEq<&str> for SharedGenString<R>
-where R: RefCounter {
-	#[inline]
-	fn eq(&self, other: &&str) -> bool {
-		self.as_str() == *other
+	#[test]
+	fn parse() {
+		assert_eq!(SharedString::from("42").parse::<u32>().unwrap(), 42);
+		assert!(SharedString::from("-1").parse::<u32>().is_err());
+		let f: f64 = SharedSyncString::from("1.5").parse().unwrap();
+		assert!((f - 1.5).abs() < f64::EPSILON);
 	}
-}
 
-impl cmp::PartialEq<SharedString> for str {
-	#[inline]
-	fn eq(&self, other: &SharedString) -> bool {
-		self == other.as_str()
+	#[test]
+	fn split_inclusive() {
+		let inputs = ["", ",", "a,b,", "a,b", ",,a", "a,,b,,"];
+		for input in &inputs {
+			let split: Vec<_> = SharedString::from(*input)
+				.split_inclusive(b',')
+				.collect();
+			let std: Vec<_> = input.split_inclusive(',').collect();
+			assert_eq!(split, std, "input {:?}", input);
+		}
 	}
-}
 
-impl cmp::PartialEq<SharedString> for &str {
-	#[inline]
-	fn eq(&self, other: &SharedString) -> bool {
-		*self == other.as_str()
+	#[test]
+	fn lines_with_terminators() {
+		let inputs = [
+			"", "\n", "a", "a\r\nb\n", "a\n\r\n\nb", "a\rb\r\n\r"
+		];
+		for input in &inputs {
+			let lines: Vec<_> = SharedString::from(*input)
+				.lines_with_terminators()
+				.collect();
+			let joined: String = lines.iter().map(|l| l.as_str()).collect();
+			assert_eq!(joined, *input);
+		}
+
+		let lines: Vec<_> = SharedString::from("a\r\nb\n")
+			.lines_with_terminators()
+			.collect();
+		assert_eq!(lines, ["a\r\n", "b\n"]);
 	}
-}
 
-impl cmp::PartialEq<SharedSyncString> for str {
-	#[inline]
-	fn eq(&self, other: &SharedSyncString) -> bool {
-		self == other.as_str()
+	#[test]
+	fn lines_like_std() {
+		let inputs = [
+			"", "\n", "\r\n", "a", "a\n", "a\nb\n", "a\r\nb\r\n", "a\n\n",
+			"foo\r\nbar\n\nbaz\r", "a\rb", "\r", "\n\r\n", "\r\r\n"
+		];
+		for input in &inputs {
+			let lines: Vec<_> = SharedString::from(*input).lines().collect();
+			let std: Vec<_> = input.lines().collect();
+			assert_eq!(lines, std, "input {:?}", input);
+		}
 	}
-}
 
-impl cmp::PartialEq<SharedSyncString> for &str {
-	#[inline]
-	fn eq(&self, other: &SharedSyncString) -> bool {
-		*self == other.as_str()
+	#[test]
+	fn patterns() {
+		let s = SharedString::from("foo好bar");
+		let foo = SharedString::from("foo");
+		let bar = SharedSyncString::from("bar");
+		let owned = "好".to_string();
+
+		assert!(s.contains('好'));
+		assert!(s.contains("o好b"));
+		assert!(s.contains(&foo));
+		assert!(s.contains(bar.clone()));
+		assert!(s.contains(&owned));
+		assert!(!s.contains('x'));
+
+		assert!(s.starts_with('f'));
+		assert!(s.starts_with("foo"));
+		assert!(s.starts_with(&foo));
+		assert!(!s.starts_with(&bar));
+
+		assert!(s.ends_with('r'));
+		assert!(s.ends_with("好bar"));
+		assert!(s.ends_with(&bar));
+		assert!(!s.ends_with(foo));
 	}
-}
 
-// need a custom ord
+	#[test]
+	fn find_str() {
+		let s = SharedString::from("foo bar baz");
+		assert_eq!(s.find_index("foo"), Some(0));
+		assert_eq!(s.find_index("bar"), Some(4));
+		assert_eq!(s.find_index("baz"), Some(8));
+		assert_eq!(s.find_index("qux"), None);
 
-impl<R> Default for SharedGenString<R>
-where R: RefCounter {
-	/// Creates a new empty `SharedString`.
-	#[inline]
-	fn default() -> Self {
-		Self::new()
+		assert_eq!(s.find_str("foo").unwrap(), "foo");
+		let bar = s.find_str("bar").unwrap();
+		assert_eq!(bar, "bar");
+		assert_eq!(bar.as_full_str(), "foo bar baz");
+		assert_eq!(s.find_str("baz").unwrap(), "baz");
+		assert!(s.find_str("qux").is_none());
 	}
-}
 
-impl<R> From<String> for SharedGenString<R>
-where R: RefCounter {
-	#[inline]
-	fn from(s: String) -> Self {
-		Self {
-			start: 0,
-			len: s.len(),
-			bytes: s.into_bytes().into_boxed_slice().into()
+	#[test]
+	fn match_indices() {
+		let inputs = [
+			("", "a"), ("aaa", "aa"), ("aaaa", "aa"), ("a,b,,c", ","),
+			("好a好好", "好"), ("abc", "x")
+		];
+		for (input, pat) in &inputs {
+			let matches: Vec<_> = SharedString::from(*input)
+				.match_indices(pat)
+				.collect();
+			let std: Vec<_> = input.match_indices(pat).collect();
+			assert_eq!(matches.len(), std.len(), "input {:?}", input);
+			for ((at, m), (n_at, n_m)) in matches.iter().zip(std) {
+				assert_eq!(*at, n_at);
+				assert_eq!(m, n_m);
+			}
 		}
-	}
-}
 
-impl<R> From<&str> for SharedGenString<R>
-where R: RefCounter {
-	#[inline]
-	fn from(s: &str) -> Self {
-		s.to_string().into()
+		// offsets are relative to the SharedString
+		let s = SharedString::from("xx a a").idx(3..);
+		let matches: Vec<_> = s.match_indices("a").collect();
+		assert_eq!(matches[0].0, 0);
+		assert_eq!(matches[1].0, 2);
 	}
-}
 
-impl<R> From<SharedGenString<R>> for String
-where R: RefCounter {
-	#[inline]
-	fn from(s: SharedGenString<R>) -> Self {
-		s.into_string()
+	#[test]
+	#[should_panic]
+	fn match_indices_empty_pattern() {
+		let _ = SharedString::from("abc").match_indices("");
 	}
-}
 
-// Tests
-#[cfg(test)]
-mod tests {
+	#[test]
+	fn replace() {
+		let s = SharedString::from("aaa bab");
 
-	use super::{SharedString, SharedSyncString};
+		let n = s.replace("x", "y");
+		assert_eq!(n, s);
+		assert_eq!(s.ref_count(), 2);
 
-	#[test]
-	fn rc() {
-		let mut hello: SharedString = "Hello, World!".into();
-		assert_eq!(hello.len(), 13);
+		let n = s.replacen("a", "b", 0);
+		assert_eq!(n, s);
+		assert_eq!(s.ref_count(), 3);
 
-		let world = hello.split_off(7);
-		assert_eq!(hello, "Hello, ");
-		assert_eq!(hello.len(), 7);
-		assert_eq!(world, "World!");
-		assert_eq!(world.len(), 6);
+		assert_eq!(s.replace("aa", "c"), "ca bab");
+		assert_eq!(s.replace("a", ""), " bb");
+		assert_eq!(s.replacen("a", "x", 2), "xxa bab");
+		assert_eq!(s.replace("", "-"), "-a-a-a- -b-a-b-");
 	}
 
 	#[test]
-	fn arc() {
-		let mut hello: SharedSyncString = "Hello, World!".into();
+	fn char_len() {
+		let ascii = SharedString::from("Hello, World!");
+		assert_eq!(ascii.len(), ascii.char_len());
 
-		let world = hello.split_off(7);
+		let s = SharedString::from("好");
+		assert_eq!(s.len(), 3);
+		assert_eq!(s.char_len(), 1);
 
-		let handle = std::thread::spawn(move || {
-			assert_eq!(world, "World!");
-			assert_eq!(world.as_full_str(), "Hello, World!");
-		});
+		let mut s = SharedString::from("Grüße, 好 World");
+		let world = s.split_off(s.len() - 5);
+		assert_eq!(s.char_len(), 9);
+		assert_eq!(world.char_len(), 5);
+		assert_eq!(SharedString::new().char_len(), 0);
+	}
 
-		assert_eq!(hello, "Hello, ");
-		handle.join().unwrap();
+	#[test]
+	fn get_chars() {
+		let s = SharedString::from("a好b");
+		assert_eq!(s.get_chars(1..2).unwrap(), "好");
+		assert_eq!(s.get_chars(..).unwrap(), "a好b");
+		assert_eq!(s.get_chars(..=1).unwrap(), "a好");
+		assert_eq!(s.get_chars(2..).unwrap(), "b");
+		assert_eq!(s.get_chars(3..).unwrap(), "");
+		assert_eq!(s.get_chars(1..1).unwrap(), "");
+		assert!(s.get_chars(4..).is_none());
+		assert!(s.get_chars(..4).is_none());
+		let (start, end) = (2, 1);
+		assert!(s.get_chars(start..end).is_none());
+
+		assert_eq!(s.idx_chars(1..=2), "好b");
 	}
 
 	#[test]
-	fn into() {
-		let hello = SharedString::from("Hello, World!");
-		let s = hello.into_string();
-		assert_eq!(s, "Hello, World!");
+	#[should_panic]
+	fn idx_chars_out_of_bounds() {
+		let _ = SharedString::from("好").idx_chars(..2);
+	}
 
-		let mut hello: SharedString = s.into();
-		let world = hello.split_off(7);
+	#[test]
+	fn into_chars() {
+		let s = SharedString::from("a好ü 😀b");
+		let chars: Vec<_> = s.clone().into_iter().collect();
+		let std: Vec<_> = s.as_str().chars().collect();
+		assert_eq!(chars, std);
 
-		let s = world.into_string();
-		assert_eq!(s, "World!");
+		let rev: String = s.clone().into_iter().rev().collect();
+		assert_eq!(rev, "b😀 ü好a");
 
-		assert!(hello != s.as_str());
+		let mut n = 0;
+		for c in s.idx(1..4) {
+			assert_eq!(c, '好');
+			n += 1;
+		}
+		assert_eq!(n, 1);
 
-		let n_hello = SharedString::from("Hello, ");
-		assert_eq!(hello, n_hello);
+		// 12 bytes are at least 3 chars
+		assert_eq!(s.clone().into_iter().size_hint(), (3, Some(12)));
+		assert_eq!(s.idx(..1).into_iter().size_hint(), (1, Some(1)));
 	}
 
 	#[test]
-	fn split_off_zero() {
-		let mut foobar = SharedString::from("foobar");
-		let n_foobar = foobar.split_off(0);
-		assert_eq!("", foobar);
-		assert_eq!("foobar", n_foobar);
+	fn alloc_only() {
+		// only uses types which are available without std
+		use alloc::{string::String, vec::Vec, borrow::Cow};
+		use core::fmt::Write;
+
+		let mut s = String::new();
+		write!(s, "{}", SharedString::from("foo bar")).unwrap();
+		let split: Vec<_> = SharedSyncString::from(s).split(b' ').collect();
+		assert_eq!(split, [Cow::Borrowed("foo"), Cow::Borrowed("bar")]);
 	}
 
 	#[test]
-	#[should_panic]
-	fn panic_char_boundary() {
-		let mut s = SharedString::from("abc 好 def");
-		let _ = s.split_off(5);
+	fn repeat() {
+		let s = SharedString::from("a好");
+		assert!(s.repeat(0).is_empty());
+		assert_eq!(s.repeat(1), s);
+		assert_eq!(s.repeat(3), "a好a好a好");
+		assert!(SharedString::new().repeat(10).is_empty());
 	}
 
 	#[test]
 	#[should_panic]
-	fn panic_length() {
-		let mut s = SharedString::from("abc");
-		let _ = s.split_off(5);
+	fn repeat_overflow() {
+		let _ = SharedString::from("ab").repeat(usize::MAX);
 	}
 
 	#[test]
-	fn range_as_str() {
-		let raw = SharedString::from("Hello, World!");
-		let hello = &raw[..5];
-		let world = &raw[7..];
+	fn concat_join() {
+		let empty: [SharedString; 0] = [];
+		assert!(SharedString::concat(&empty).is_empty());
+		assert!(SharedString::join(&empty, ", ").is_empty());
 
-		assert_eq!(hello, "Hello");
-		assert_eq!(world, "World!");
+		let single = [SharedString::from("foo")];
+		let foo = SharedString::join(&single, ", ");
+		assert_eq!(foo, "foo");
+		assert_eq!(single[0].ref_count(), 2);
+
+		let parts: Vec<_> = SharedString::from("a b c").split(b' ').collect();
+		assert_eq!(SharedString::concat(&parts), "abc");
+		assert_eq!(SharedString::join(&parts, ", "), "a, b, c");
+		assert_eq!(SharedString::join(&parts, ", ").len(), 7);
 	}
 
 	#[test]
-	fn range_with_get() {
-		let raw = SharedString::from("Hello, World!");
-		let hello = raw.get(..5).unwrap();
-		let world = raw.get(7..).unwrap();
+	fn clear() {
+		let mut foo = SharedString::from("foobar");
+		let bar = foo.split_off(3);
+		assert_eq!(bar.ref_count(), 2);
 
-		assert_eq!(hello, "Hello");
-		assert_eq!(world, "World!");
+		foo.clear();
+		assert!(foo.is_empty());
+		assert_eq!(foo.as_full_str(), "");
+		assert_eq!(bar, "bar");
+		assert!(bar.is_unique());
 	}
 
 	#[test]
-	fn range_with_idx() {
-		let raw = SharedString::from("Hello, World!");
-		let hello = raw.idx(..5);
-		let world = raw.idx(7..);
+	fn truncate_checked() {
+		let mut s = SharedString::from("ab好cd");
+		assert!(!s.truncate_checked(3));
+		assert!(!s.truncate_checked(4));
+		assert!(!s.truncate_checked(8));
+		assert_eq!(s, "ab好cd");
 
-		assert_eq!(hello, "Hello");
-		assert_eq!(world, "World!");
+		assert!(s.truncate_checked(7));
+		assert_eq!(s, "ab好cd");
+		assert!(s.truncate_checked(5));
+		assert_eq!(s, "ab好");
+		assert!(s.truncate_checked(0));
+		assert!(s.is_empty());
 	}
 
 	#[test]
-	fn empty() {
-		let s = SharedString::from("");
-		assert_eq!(s.len(), 0);
-		assert!(s.is_empty());
+	fn from_char() {
+		let a = SharedString::from('a');
+		assert_eq!(a, "a");
+		assert_eq!(a.len(), 1);
 
-		assert!(s.get(..).is_some());
-		assert!(s.idx(..).is_empty());
-		assert!(s.get(1..).is_none());
+		let hao = SharedSyncString::from('好');
+		assert_eq!(hao, "好");
+		assert_eq!(hao.len(), 3);
 	}
 
 	#[test]
-	fn equal() {
-		let rc: SharedString = "Hello, World!".into();
-		let arc: SharedSyncString = "Hello, World!".into();
-		assert_eq!(rc, arc);
+	fn from_iter() {
+		let s = SharedString::from("a b c");
+		let joined: SharedString = s.clone().split(b' ').collect();
+		assert_eq!(joined, "abc");
+
+		let sync: SharedSyncString = s.clone().split(b' ').collect();
+		assert_eq!(sync, "abc");
+
+		let chars: SharedString = s.chars().filter(|c| *c != ' ').collect();
+		assert_eq!(chars, "abc");
+
+		let rev: SharedString = s.into_iter().rev().collect();
+		assert_eq!(rev, "c b a");
 	}
 
 	#[test]
-	fn split() {
-		let fullname = SharedString::from("Albert Einstein");
-		let mut split = fullname.split(b' ');
-		assert_eq!(split.next().unwrap(), "Albert");
-		assert_eq!(split.next().unwrap(), "Einstein");
-		assert_eq!(split.next(), None);
+	fn extend() {
+		let mut foo = SharedString::from("foo");
+		let clone = foo.clone();
+
+		foo.extend(vec!["bar", "baz"]);
+		assert_eq!(foo, "foobarbaz");
+		assert_eq!(clone, "foo");
+		assert!(clone.is_unique());
+
+		foo.extend("!?".chars());
+		assert_eq!(foo, "foobarbaz!?");
+
+		// nothing to append, nothing gets copied
+		let shared = foo.clone();
+		foo.extend("".chars());
+		foo.extend(Vec::<&str>::new());
+		assert!(!shared.is_unique());
+
+		let mut empty = SharedString::new();
+		empty.extend(Vec::<&str>::new());
+		assert!(empty.is_empty());
 	}
 
 	#[test]
-	fn lines() {
-		let quote = SharedString::from("Wenn die Menschen nur über das sprächen,\nwas sie begreifen,\r\ndann würde es sehr still auf der Welt sein.\n\r\n");
-		let mut lines = quote.lines();
-		assert_eq!(
-			lines.next().unwrap(),
-			"Wenn die Menschen nur über das sprächen,"
-		);
-		assert_eq!(lines.next().unwrap(), "was sie begreifen,");
-		assert_eq!(
-			lines.next().unwrap(),
-			"dann würde es sehr still auf der Welt sein."
-		);
-		assert_eq!(lines.next().unwrap(), "");
-		assert_eq!(lines.next(), None);
+	fn builder() {
+		use super::SharedStringBuilder;
 
-		let empty = SharedString::from(" ");
-		let mut lines = empty.lines();
-		assert_eq!(" ", lines.next().unwrap());
-		assert_eq!(lines.next(), None);
+		let mut builder = SharedStringBuilder::new();
+		assert!(builder.is_empty());
+		builder.push_str("foo");
+		builder.push('好');
+		assert_eq!(builder.len(), 6);
+		assert_eq!(builder.as_str(), "foo好");
+
+		let frozen: SharedString = builder.freeze();
+		assert_eq!(frozen, "foo好");
+		assert!(frozen.is_unique());
+
+		let foo = frozen.idx(..3);
+		assert_eq!(foo.as_full_bytes().as_ptr(), frozen.as_full_bytes().as_ptr());
+		assert_eq!(frozen.ref_count(), 2);
+
+		// exact capacity, so the bytes don't get copied
+		let mut builder = SharedStringBuilder::with_capacity(3);
+		builder.push_str("bar");
+		let ptr = builder.as_str().as_ptr();
+		let bar = SharedSyncString::from(builder);
+		assert_eq!(bar, "bar");
+		assert_eq!(bar.as_full_bytes().as_ptr(), ptr);
 	}
 
 	#[test]
-	fn range_eq_str_range() {
-		let line = "foo: bar";
-		let at = line.find(':').unwrap();
-		let key = &line[..at];
-		let value = &line[(at + 2)..];
+	fn builder_fmt_write() {
+		use super::SharedStringBuilder;
+		use std::fmt::Write;
 
-		assert_eq!(key, "foo");
-		assert_eq!(value, "bar");
+		let mut builder = SharedStringBuilder::new();
+		for (key, value) in [("a", 1), ("b", 2)].iter() {
+			writeln!(builder, "{}={:02}", key, value).unwrap();
+		}
+		builder.write_char('!').unwrap();
 
-		let line = SharedString::from(line);
-		let key = line.idx(..at);
-		let value = line.idx((at + 2)..);
+		let s: SharedString = builder.freeze();
+		assert_eq!(s, "a=01\nb=02\n!");
+		let lines: Vec<_> = s.lines().collect();
+		assert_eq!(lines, ["a=01", "b=02", "!"]);
+	}
 
-		assert_eq!(key, "foo");
-		assert_eq!(value, "bar");
+	#[test]
+	fn into_builder() {
+		// unique
+		let foo = SharedString::from("foo");
+		let ptr = foo.as_full_bytes().as_ptr();
+		let builder = foo.into_builder();
+		assert_eq!(builder.as_str(), "foo");
+		assert_eq!(builder.as_str().as_ptr(), ptr);
+
+		// shared
+		let foo = SharedString::from("foo");
+		let clone = foo.clone();
+		let mut builder = foo.into_builder();
+		assert_ne!(builder.as_str().as_ptr(), clone.as_full_bytes().as_ptr());
+		builder.push_str("bar");
+		let foobar: SharedString = builder.freeze();
+		assert_eq!(foobar, "foobar");
+		assert_eq!(clone, "foo");
 	}
 
 	#[test]
-	fn range_in_range() {
-		let line = "date: Mon, 30 Nov 2020 22:16:22 GMT\nserver: mw1271.eqiad.wmnet\nx-content-type-options: nosniff";
-		let mut lines = SharedString::from(line).lines();
+	fn cow() {
+		use std::borrow::Cow;
 
-		let _ = lines.next().unwrap();
-		let line = lines.next().unwrap();
+		fn takes_cow(c: Cow<'_, str>) -> usize {
+			c.len()
+		}
 
-		let at = line.find(':').unwrap();
-		assert_eq!(at, 6);
+		let mut foo = SharedString::from("foobar");
+		let bar = foo.split_off(3);
+		assert!(matches!(foo.as_cow(), Cow::Borrowed("foo")));
+		assert_eq!(takes_cow(bar.as_cow()), 3);
 
-		let key = line.idx(..at);
-		assert_eq!(key, "server");
+		let owned = bar.into_cow();
+		assert!(matches!(&owned, Cow::Owned(s) if s == "bar"));
 
-		let value = line.idx((at + 2)..);
-		assert_eq!(value, "mw1271.eqiad.wmnet");
+		let cow: Cow<'static, str> = foo.into();
+		assert_eq!(cow, "foo");
 	}
 
 	#[test]
-	fn truncate() {
-		let mut foobar = SharedString::from("foobar");
-		foobar.truncate(3);
-		assert_eq!(foobar, "foo");
+	fn trim_matches() {
+		let s = SharedString::from("\"\"quoted\"");
+		assert_eq!(s.trim_matches('"'), "quoted");
+		assert_eq!(s.trim_start_matches('"'), "quoted\"");
+		assert_eq!(s.trim_end_matches('"'), "\"\"quoted");
+		assert_eq!(s.trim_matches('"').as_full_str(), s.as_str());
+
+		let s = SharedString::from("abababcab");
+		assert_eq!(s.trim_start_matches("ab"), "cab");
+		assert_eq!(s.trim_end_matches("ab"), "abababc");
+		assert_eq!(s.trim_matches("ab"), "c");
+		assert_eq!(s.trim_matches(SharedString::from("ab")), "c");
+
+		let s = SharedString::from("xxx");
+		assert!(s.trim_matches('x').is_empty());
+	}
+
+	#[test]
+	fn split_like_split_terminator() {
+		let inputs = ["", ",", "a,b,", "a,b", ",a", "a,,b,,", ",,"];
+		for input in &inputs {
+			let split: Vec<_> = SharedString::from(*input)
+				.split(b',')
+				.collect();
+			let std: Vec<_> = input.split_terminator(',').collect();
+			assert_eq!(split, std, "input {:?}", input);
+		}
 	}
 }
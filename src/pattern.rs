@@ -0,0 +1,166 @@
+//! Pattern types
+
+use crate::{SharedGenString, RefCounter};
+
+use alloc::string::String;
+
+/// A pattern which can be searched for in a string.
+///
+/// Implemented for `char`, `&str`, `&String` and `SharedString`.
+///
+/// Will be replaced when [Pattern](https://doc.rust-lang.org/std/str/pattern/trait.Pattern.html) gets stabilized.
+pub trait Pattern {
+	/// Returns `true` if the pattern matches anywhere in the `haystack`.
+	fn is_contained_in(&self, haystack: &str) -> bool;
+
+	/// Returns `true` if the pattern matches at the start of the `haystack`.
+	fn is_prefix_of(&self, haystack: &str) -> bool;
+
+	/// Returns `true` if the pattern matches at the end of the `haystack`.
+	fn is_suffix_of(&self, haystack: &str) -> bool;
+
+	/// Returns the `haystack` with all prefixes that match the pattern
+	/// repeatedly removed.
+	fn trim_start_of<'a>(&self, haystack: &'a str) -> &'a str;
+
+	/// Returns the `haystack` with all suffixes that match the pattern
+	/// repeatedly removed.
+	fn trim_end_of<'a>(&self, haystack: &'a str) -> &'a str;
+}
+
+impl Pattern for char {
+	#[inline]
+	fn is_contained_in(&self, haystack: &str) -> bool {
+		haystack.contains(*self)
+	}
+
+	#[inline]
+	fn is_prefix_of(&self, haystack: &str) -> bool {
+		haystack.starts_with(*self)
+	}
+
+	#[inline]
+	fn is_suffix_of(&self, haystack: &str) -> bool {
+		haystack.ends_with(*self)
+	}
+
+	#[inline]
+	fn trim_start_of<'a>(&self, haystack: &'a str) -> &'a str {
+		haystack.trim_start_matches(*self)
+	}
+
+	#[inline]
+	fn trim_end_of<'a>(&self, haystack: &'a str) -> &'a str {
+		haystack.trim_end_matches(*self)
+	}
+}
+
+impl Pattern for &str {
+	#[inline]
+	fn is_contained_in(&self, haystack: &str) -> bool {
+		haystack.contains(*self)
+	}
+
+	#[inline]
+	fn is_prefix_of(&self, haystack: &str) -> bool {
+		haystack.starts_with(*self)
+	}
+
+	#[inline]
+	fn is_suffix_of(&self, haystack: &str) -> bool {
+		haystack.ends_with(*self)
+	}
+
+	#[inline]
+	fn trim_start_of<'a>(&self, haystack: &'a str) -> &'a str {
+		haystack.trim_start_matches(*self)
+	}
+
+	#[inline]
+	fn trim_end_of<'a>(&self, haystack: &'a str) -> &'a str {
+		haystack.trim_end_matches(*self)
+	}
+}
+
+impl Pattern for &String {
+	#[inline]
+	fn is_contained_in(&self, haystack: &str) -> bool {
+		self.as_str().is_contained_in(haystack)
+	}
+
+	#[inline]
+	fn is_prefix_of(&self, haystack: &str) -> bool {
+		self.as_str().is_prefix_of(haystack)
+	}
+
+	#[inline]
+	fn is_suffix_of(&self, haystack: &str) -> bool {
+		self.as_str().is_suffix_of(haystack)
+	}
+
+	#[inline]
+	fn trim_start_of<'a>(&self, haystack: &'a str) -> &'a str {
+		self.as_str().trim_start_of(haystack)
+	}
+
+	#[inline]
+	fn trim_end_of<'a>(&self, haystack: &'a str) -> &'a str {
+		self.as_str().trim_end_of(haystack)
+	}
+}
+
+impl<R> Pattern for SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn is_contained_in(&self, haystack: &str) -> bool {
+		self.as_str().is_contained_in(haystack)
+	}
+
+	#[inline]
+	fn is_prefix_of(&self, haystack: &str) -> bool {
+		self.as_str().is_prefix_of(haystack)
+	}
+
+	#[inline]
+	fn is_suffix_of(&self, haystack: &str) -> bool {
+		self.as_str().is_suffix_of(haystack)
+	}
+
+	#[inline]
+	fn trim_start_of<'a>(&self, haystack: &'a str) -> &'a str {
+		self.as_str().trim_start_of(haystack)
+	}
+
+	#[inline]
+	fn trim_end_of<'a>(&self, haystack: &'a str) -> &'a str {
+		self.as_str().trim_end_of(haystack)
+	}
+}
+
+impl<R> Pattern for &SharedGenString<R>
+where R: RefCounter {
+	#[inline]
+	fn is_contained_in(&self, haystack: &str) -> bool {
+		self.as_str().is_contained_in(haystack)
+	}
+
+	#[inline]
+	fn is_prefix_of(&self, haystack: &str) -> bool {
+		self.as_str().is_prefix_of(haystack)
+	}
+
+	#[inline]
+	fn is_suffix_of(&self, haystack: &str) -> bool {
+		self.as_str().is_suffix_of(haystack)
+	}
+
+	#[inline]
+	fn trim_start_of<'a>(&self, haystack: &'a str) -> &'a str {
+		self.as_str().trim_start_of(haystack)
+	}
+
+	#[inline]
+	fn trim_end_of<'a>(&self, haystack: &'a str) -> &'a str {
+		self.as_str().trim_end_of(haystack)
+	}
+}
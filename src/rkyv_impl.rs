@@ -0,0 +1,76 @@
+//! Rkyv implementations
+//!
+//! A `SharedString` is archived as an `ArchivedString`.
+
+use crate::{SharedGenString, RefCounter};
+
+use rkyv::{Archive, Serialize, Deserialize, Place};
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::rancor::{Fallible, Source};
+use rkyv::ser::Writer;
+
+impl<R> Archive for SharedGenString<R>
+where R: RefCounter {
+	type Archived = ArchivedString;
+	type Resolver = StringResolver;
+
+	#[inline]
+	fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+		ArchivedString::resolve_from_str(self.as_str(), resolver, out);
+	}
+}
+
+impl<R, S> Serialize<S> for SharedGenString<R>
+where
+	R: RefCounter,
+	S: Fallible + Writer + ?Sized,
+	S::Error: Source {
+	fn serialize(&self, serializer: &mut S) -> Result<StringResolver, S::Error> {
+		ArchivedString::serialize_from_str(self.as_str(), serializer)
+	}
+}
+
+impl<R, D> Deserialize<SharedGenString<R>, D> for ArchivedString
+where
+	R: RefCounter,
+	D: Fallible + ?Sized {
+	fn deserialize(&self, _: &mut D) -> Result<SharedGenString<R>, D::Error> {
+		Ok(self.as_str().into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{SharedString, SharedSyncString};
+
+	use rkyv::rancor::Error;
+	use rkyv::string::ArchivedString;
+
+	#[test]
+	fn round_trip() {
+		let mut hello = SharedString::from("Hello, Wörld!");
+		let world = hello.split_off(7);
+
+		let bytes = rkyv::to_bytes::<Error>(&world).unwrap();
+		let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+		assert_eq!(archived.as_str(), "Wörld!");
+
+		let n_world: SharedSyncString = rkyv::deserialize::<_, Error>(archived)
+			.unwrap();
+		assert_eq!(n_world, world);
+		assert_eq!(n_world.as_full_str(), "Wörld!");
+	}
+
+	#[test]
+	fn invalid_utf8() {
+		let bytes = rkyv::to_bytes::<Error>(&SharedString::from("ab"))
+			.unwrap();
+		let mut bytes = bytes.to_vec();
+		// short strings are stored inline, replace the b
+		// with a lone continuation byte
+		assert_eq!(&bytes[..2], b"ab");
+		bytes[1] = 0x80;
+		assert!(rkyv::access::<ArchivedString, Error>(&bytes).is_err());
+	}
+}
@@ -0,0 +1,155 @@
+//! Serialize a `SharedString` as bytes
+//!
+//! Only available with the `serde` feature.
+//!
+//! Use this module with `#[serde(with = "shared_string::serde_bytes")]` to
+//! encode a `SharedString` as raw bytes instead of a string. When
+//! deserializing the bytes are validated to be UTF-8.
+//!
+//! ## Example
+//!
+//! ```
+//! use shared_string::SharedString;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Packet {
+//!     #[serde(with = "shared_string::serde_bytes")]
+//!     payload: SharedString
+//! }
+//! ```
+
+use crate::{SharedGenString, RefCounter};
+
+use core::fmt;
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+use alloc::string::String;
+
+use serde::{Serializer, Deserializer};
+use serde::de::{self, Visitor, SeqAccess};
+
+/// Serializes a `SharedString` as bytes.
+pub fn serialize<R, S>(
+	s: &SharedGenString<R>,
+	serializer: S
+) -> Result<S::Ok, S::Error>
+where
+	R: RefCounter,
+	S: Serializer {
+	serializer.serialize_bytes(s.as_bytes())
+}
+
+/// Deserializes a `SharedString` from bytes, validating that they are UTF-8.
+pub fn deserialize<'de, R, D>(
+	deserializer: D
+) -> Result<SharedGenString<R>, D::Error>
+where
+	R: RefCounter,
+	D: Deserializer<'de> {
+	deserializer.deserialize_byte_buf(BytesVisitor {
+		marker: PhantomData
+	})
+}
+
+struct BytesVisitor<R> {
+	marker: PhantomData<R>
+}
+
+impl<'de, R> Visitor<'de> for BytesVisitor<R>
+where R: RefCounter {
+	type Value = SharedGenString<R>;
+
+	fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("UTF-8 bytes")
+	}
+
+	fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+	where E: de::Error {
+		self.visit_byte_buf(v.to_vec())
+	}
+
+	// doesn't need to copy
+	fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+	where E: de::Error {
+		SharedGenString::from_utf8(v)
+			.map_err(|e| de::Error::invalid_value(
+				de::Unexpected::Bytes(e.as_bytes()),
+				&self
+			))
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+	where E: de::Error {
+		Ok(v.into())
+	}
+
+	fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+	where E: de::Error {
+		Ok(v.into())
+	}
+
+	// formats without a bytes type encode them as a sequence
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where A: SeqAccess<'de> {
+		let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+		while let Some(b) = seq.next_element()? {
+			bytes.push(b);
+		}
+
+		self.visit_byte_buf(bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{SharedString, SharedSyncString};
+
+	use serde::{Serialize, Deserialize};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Packet {
+		#[serde(with = "crate::serde_bytes")]
+		method: SharedString,
+		#[serde(with = "crate::serde_bytes")]
+		body: SharedSyncString
+	}
+
+	#[test]
+	fn bincode_round_trip() {
+		let mut raw = SharedString::from("GET hällo");
+		let body = raw.split_off(4);
+		let packet = Packet {
+			method: raw.idx(..3),
+			body: body.as_str().into()
+		};
+
+		let bytes = bincode::serialize(&packet).unwrap();
+		// 8 bytes length prefix for every field
+		assert_eq!(bytes.len(), 8 + 3 + 8 + 6);
+
+		let n_packet: Packet = bincode::deserialize(&bytes).unwrap();
+		assert_eq!(packet, n_packet);
+	}
+
+	#[test]
+	fn invalid_utf8() {
+		let bytes = bincode::serialize(&(&b"GET"[..], &b"\xff"[..])).unwrap();
+		assert!(bincode::deserialize::<Packet>(&bytes).is_err());
+	}
+
+	#[test]
+	fn json_round_trip() {
+		let packet = Packet {
+			method: "GET".into(),
+			body: "ä".into()
+		};
+
+		let json = serde_json::to_string(&packet).unwrap();
+		assert_eq!(json, r#"{"method":[71,69,84],"body":[195,164]}"#);
+
+		let n_packet: Packet = serde_json::from_str(&json).unwrap();
+		assert_eq!(packet, n_packet);
+	}
+}
@@ -0,0 +1,99 @@
+//! Serde implementations
+
+use crate::{SharedGenString, RefCounter};
+
+use core::fmt;
+use core::marker::PhantomData;
+use alloc::string::String;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor};
+
+impl<R> Serialize for SharedGenString<R>
+where R: RefCounter {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: Serializer {
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+struct SharedStringVisitor<R> {
+	marker: PhantomData<R>
+}
+
+impl<'de, R> Visitor<'de> for SharedStringVisitor<R>
+where R: RefCounter {
+	type Value = SharedGenString<R>;
+
+	fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("a string")
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+	where E: de::Error {
+		Ok(v.into())
+	}
+
+	// doesn't need to copy
+	fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+	where E: de::Error {
+		Ok(v.into())
+	}
+}
+
+impl<'de, R> Deserialize<'de> for SharedGenString<R>
+where R: RefCounter {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		deserializer.deserialize_string(SharedStringVisitor {
+			marker: PhantomData
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{SharedString, SharedSyncString};
+
+	use serde::{Serialize, Deserialize};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Name {
+		firstname: SharedString,
+		middlename: SharedString,
+		lastname: SharedSyncString
+	}
+
+	#[test]
+	fn round_trip() {
+		let name = Name {
+			firstname: "Bartholomew".into(),
+			middlename: "Jojo".into(),
+			lastname: "Simpson".into()
+		};
+
+		let json = serde_json::to_string(&name).unwrap();
+		assert_eq!(
+			json,
+			r#"{"firstname":"Bartholomew","middlename":"Jojo","lastname":"Simpson"}"#
+		);
+
+		let n_name: Name = serde_json::from_str(&json).unwrap();
+		assert_eq!(name, n_name);
+	}
+
+	#[test]
+	fn split_value() {
+		let mut hello = SharedString::from("Hello, World!");
+		let world = hello.split_off(7);
+
+		let json = serde_json::to_string(&world).unwrap();
+		assert_eq!(json, r#""World!""#);
+
+		let world: SharedString = serde_json::from_str(&json).unwrap();
+		assert_eq!(world.as_full_str(), "World!");
+
+		assert!(serde_json::from_str::<SharedString>("42").is_err());
+	}
+}
@@ -0,0 +1,82 @@
+//! Proptest strategies
+//!
+//! Only available with the `proptest` feature.
+
+use crate::{SharedGenString, RefCounter};
+
+use core::fmt;
+use alloc::string::String;
+
+use proptest::prelude::*;
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::BoxedStrategy;
+
+/// Returns a strategy which generates `SharedString`s.
+///
+/// Besides arbitrary strings it favours interesting cases like empty strings,
+/// multi-byte characters, newlines and common delimiters.
+///
+/// ## Example
+///
+/// ```
+/// use shared_string::SharedString;
+/// use shared_string::strategy::shared_string;
+/// use proptest::strategy::{Strategy, ValueTree};
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::default();
+/// let tree = shared_string().new_tree(&mut runner).unwrap();
+/// let s: SharedString = tree.current();
+/// assert_eq!(s.len(), s.as_str().len());
+/// ```
+pub fn shared_string<R>() -> BoxedStrategy<SharedGenString<R>>
+where R: RefCounter + fmt::Debug + 'static {
+	prop_oneof![
+		Just(String::new()),
+		any::<String>(),
+		"[a-z0-9 ,:;\t\r\n]*",
+		"[aäöü€😀 \n]*"
+	]
+		.prop_map(SharedGenString::from)
+		.boxed()
+}
+
+impl<R> Arbitrary for SharedGenString<R>
+where R: RefCounter + fmt::Debug + 'static {
+	type Parameters = ();
+	type Strategy = BoxedStrategy<Self>;
+
+	fn arbitrary_with(_: ()) -> Self::Strategy {
+		shared_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::SharedString;
+
+	proptest! {
+		#[test]
+		fn split_rejoins(s in any::<SharedString>()) {
+			let joined = s.clone()
+				.split(b' ')
+				.map(|s| s.to_string())
+				.collect::<Vec<_>>()
+				.join(" ");
+
+			// a trailing space does not produce an empty segment
+			let expected = s.strip_suffix(" ").unwrap_or(s.clone());
+			prop_assert_eq!(joined, expected.as_str());
+		}
+
+		#[test]
+		fn lines_like_str(s in shared_string::<_>()) {
+			let s: SharedString = s;
+			let lines: Vec<_> = s.clone().lines().collect();
+			let std: Vec<_> = s.as_str().lines().collect();
+			prop_assert_eq!(lines, std);
+		}
+	}
+}